@@ -0,0 +1,14 @@
+//! Amounts followed by an asset symbol, such as `1.5USD`.
+
+#![no_main]
+
+use common::{Euro, ParseMode, PositiveAsset, UnsignedAsset, Usd};
+use fuzz::check_round_trip;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    for mode in [ParseMode::Strict, ParseMode::Lenient] {
+        check_round_trip(s, |s| UnsignedAsset::<Usd>::parse_with(s, mode));
+        check_round_trip(s, |s| PositiveAsset::<Euro>::parse_with(s, mode));
+    }
+});
@@ -0,0 +1,24 @@
+//! The string parsers in numeric, in both grammars.
+
+#![no_main]
+
+use fuzz::check_round_trip;
+use libfuzzer_sys::fuzz_target;
+use numeric::{
+    BasisPoints, ParseMode, Percent, PositiveDecimal, Ratio, Rational, ScaledUnsignedDecimal,
+    SignedDecimal, UnsignedDecimal,
+};
+
+fuzz_target!(|s: &str| {
+    for mode in [ParseMode::Strict, ParseMode::Lenient] {
+        check_round_trip(s, |s| UnsignedDecimal::parse_with(s, mode));
+        check_round_trip(s, |s| PositiveDecimal::parse_with(s, mode));
+        check_round_trip(s, |s| SignedDecimal::parse_with(s, mode));
+        check_round_trip(s, |s| ScaledUnsignedDecimal::<0>::parse_with(s, mode));
+        check_round_trip(s, |s| ScaledUnsignedDecimal::<18>::parse_with(s, mode));
+        check_round_trip(s, |s| Ratio::parse_with(s, mode));
+        check_round_trip(s, |s| Percent::parse_with(s, mode));
+        check_round_trip(s, |s| BasisPoints::parse_with(s, mode));
+        check_round_trip(s, |s| Rational::parse_with(s, mode));
+    }
+});
@@ -0,0 +1,14 @@
+//! Prices such as `1.1 USD/EURO`, with decimal and rational values.
+
+#![no_main]
+
+use common::{Euro, ParseMode, Price, Rational, Usd};
+use fuzz::check_round_trip;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|s: &str| {
+    for mode in [ParseMode::Strict, ParseMode::Lenient] {
+        check_round_trip(s, |s| Price::<Usd, Euro>::parse_with(s, mode));
+        check_round_trip(s, |s| Price::<Usd, Euro, Rational>::parse_with(s, mode));
+    }
+});
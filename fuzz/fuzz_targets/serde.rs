@@ -0,0 +1,36 @@
+//! The serde visitors, including the alternative representations in
+//! `numeric::serde`.
+
+#![no_main]
+
+use common::{Euro, PositiveAsset, Price, Rational, UnsignedAsset, Usd};
+use fuzz::check_json_round_trip;
+use libfuzzer_sys::fuzz_target;
+use numeric::{BasisPoints, Percent, PositiveDecimal, Ratio, SignedDecimal, UnsignedDecimal};
+
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Numbers {
+    #[serde(with = "numeric::serde::number")]
+    unsigned: UnsignedDecimal,
+    #[serde(with = "numeric::serde::number")]
+    signed: SignedDecimal,
+    #[serde(with = "numeric::serde::raw")]
+    positive: PositiveDecimal,
+    #[serde(with = "numeric::serde::raw")]
+    raw_signed: SignedDecimal,
+}
+
+fuzz_target!(|data: &[u8]| {
+    check_json_round_trip::<UnsignedDecimal>(data);
+    check_json_round_trip::<PositiveDecimal>(data);
+    check_json_round_trip::<SignedDecimal>(data);
+    check_json_round_trip::<Ratio>(data);
+    check_json_round_trip::<Percent>(data);
+    check_json_round_trip::<BasisPoints>(data);
+    check_json_round_trip::<Rational>(data);
+    check_json_round_trip::<Numbers>(data);
+    check_json_round_trip::<UnsignedAsset<Usd>>(data);
+    check_json_round_trip::<PositiveAsset<Euro>>(data);
+    check_json_round_trip::<Price<Usd, Euro>>(data);
+    check_json_round_trip::<Price<Usd, Euro, Rational>>(data);
+});
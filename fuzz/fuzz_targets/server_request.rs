@@ -0,0 +1,17 @@
+//! Requests as received by the server.
+
+#![no_main]
+
+use common::ServerRequest;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // ServerRequest has no PartialEq, so compare the JSON instead.
+    let Ok(request) = serde_json::from_slice::<ServerRequest>(data) else {
+        return;
+    };
+    let json = serde_json::to_string(&request).expect("serializing never fails");
+    let parsed = serde_json::from_str::<ServerRequest>(&json)
+        .unwrap_or_else(|e| panic!("serialized as {json}, which failed to parse: {e}"));
+    assert_eq!(serde_json::to_string(&parsed).unwrap(), json);
+});
@@ -0,0 +1,38 @@
+//! Shared checks for the fuzz targets.
+
+use std::fmt::{Debug, Display};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Parse `s`, and if that succeeds, check that the value renders with
+/// [Display] to a string which parses back to the same value.
+pub fn check_round_trip<T, E>(s: &str, parse: impl Fn(&str) -> Result<T, E>)
+where
+    T: Display + PartialEq + Debug,
+    E: Display,
+{
+    let Ok(value) = parse(s) else {
+        return;
+    };
+    let rendered = value.to_string();
+    match parse(&rendered) {
+        Ok(parsed) => assert_eq!(parsed, value, "{s:?} rendered as {rendered:?}"),
+        Err(e) => panic!("{s:?} rendered as {rendered:?}, which failed to parse: {e}"),
+    }
+}
+
+/// Deserialize JSON, and if that succeeds, check that serializing and
+/// deserializing again gives the same value.
+pub fn check_json_round_trip<T>(data: &[u8])
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let Ok(value) = serde_json::from_slice::<T>(data) else {
+        return;
+    };
+    let json = serde_json::to_string(&value).expect("serializing never fails");
+    match serde_json::from_str::<T>(&json) {
+        Ok(parsed) => assert_eq!(parsed, value, "serialized as {json}"),
+        Err(e) => panic!("{value:?} serialized as {json}, which failed to parse: {e}"),
+    }
+}
@@ -109,7 +109,11 @@ async fn sell_asset_inner(owner: Owner, to_sell: ToSell) -> Result<String> {
                     dollars,
                 })
                 .await?;
-            Ok(format!("Sold {dollars} for {euros_bought}"))
+            Ok(format!(
+                "Sold {} for {}",
+                dollars.display(),
+                euros_bought.display()
+            ))
         }
         ToSell::Euros(euros) => {
             let SellEurosResp { dollars_bought } =
@@ -118,7 +122,11 @@ async fn sell_asset_inner(owner: Owner, to_sell: ToSell) -> Result<String> {
                     euros,
                 })
                 .await?;
-            Ok(format!("Sold {euros} for {dollars_bought}"))
+            Ok(format!(
+                "Sold {} for {}",
+                euros.display(),
+                dollars_bought.display()
+            ))
         }
     }
 }
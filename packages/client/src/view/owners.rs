@@ -19,9 +19,9 @@ pub(super) fn Owners() -> impl IntoView {
                         <p>
                             {balance.owner.to_string()}
                             " has "
-                            {balance.dollars.to_string()}
+                            {balance.dollars.display().to_string()}
                             " and "
-                            {balance.euros.to_string()}
+                            {balance.euros.display().to_string()}
                         </p>
                     }
                 }
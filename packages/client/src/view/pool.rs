@@ -13,9 +13,9 @@ pub(super) fn Pool() -> impl IntoView {
         Some(Ok(status)) => view! {
             <dl>
                 <dt>Total USD minted</dt>
-                <dd>{status.total_usd.into_decimal().to_string()}</dd>
+                <dd>{format!("{:#.2}", status.total_usd.into_decimal())}</dd>
                 <dt>Total EURO minted</dt>
-                <dd>{status.total_euro.into_decimal().to_string()}</dd>
+                <dd>{format!("{:#.2}", status.total_euro.into_decimal())}</dd>
                 <dt>Price of 1 USD</dt>
                 <dd>{status.price_usd.to_string()}</dd>
                 <dt>Price of 1 EURO</dt>
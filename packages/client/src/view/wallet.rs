@@ -8,8 +8,8 @@ pub(super) fn Wallet() -> impl IntoView {
     let mint_dollars = Action::new(move |()| {
         query::mint_funds(
             Owner(owner.get()),
-            "100USD".parse().unwrap(),
-            "0EURO".parse().unwrap(),
+            usd!(100).into_unsigned(),
+            UnsignedAsset::zero(Euro),
             set_status,
         )
     });
@@ -17,8 +17,8 @@ pub(super) fn Wallet() -> impl IntoView {
     let mint_euros = Action::new(move |()| {
         query::mint_funds(
             Owner(owner.get()),
-            "0USD".parse().unwrap(),
-            "100EURO".parse().unwrap(),
+            UnsignedAsset::zero(Usd),
+            euro!(100).into_unsigned(),
             set_status,
         )
     });
@@ -26,7 +26,7 @@ pub(super) fn Wallet() -> impl IntoView {
     let sell_dollars = Action::new(move |()| {
         query::sell_asset(
             Owner(owner.get()),
-            query::ToSell::Dollars("10USD".parse().unwrap()),
+            query::ToSell::Dollars(usd!(10)),
             set_status,
         )
     });
@@ -34,7 +34,7 @@ pub(super) fn Wallet() -> impl IntoView {
     let sell_euros = Action::new(move |()| {
         query::sell_asset(
             Owner(owner.get()),
-            query::ToSell::Euros("10EURO".parse().unwrap()),
+            query::ToSell::Euros(euro!(10)),
             set_status,
         )
     });
@@ -0,0 +1,68 @@
+//! Benchmarks for the swap path, see BENCHMARKS.md for the baseline results.
+
+use std::hint::black_box;
+
+use common::{
+    euro, usd, Euro, NumericError, PositiveAsset, Price, ServerRequest, UnsignedAsset, Usd,
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+
+struct Pool {
+    usd: PositiveAsset<Usd>,
+    euro: PositiveAsset<Euro>,
+}
+
+/// The same steps as `sell_dollars` in the server, without the locking:
+/// check the trader's balance, [common::swap] through the pool, and credit
+/// the euros bought.
+fn sell_dollars(
+    pool: &mut Pool,
+    trader: &mut (UnsignedAsset<Usd>, UnsignedAsset<Euro>),
+    dollars: PositiveAsset<Usd>,
+) -> Result<PositiveAsset<Euro>, NumericError> {
+    let owner_usd = trader.0.checked_sub(dollars.into_unsigned())?;
+    let trade = common::swap(pool.usd, pool.euro, dollars)?;
+    let owner_euro = trader.1.checked_add(trade.bought.into_unsigned())?;
+
+    *trader = (owner_usd, owner_euro);
+    pool.usd = trade.pool_sold;
+    pool.euro = trade.pool_bought;
+    Ok(trade.bought)
+}
+
+fn swap(c: &mut Criterion) {
+    let mut group = c.benchmark_group("swap");
+    group.bench_function("sell_dollars", |b| {
+        b.iter(|| {
+            let mut pool = Pool {
+                usd: usd!(103000),
+                euro: euro!(100000),
+            };
+            let mut trader = (usd!(1000).into_unsigned(), UnsignedAsset::zero(Euro));
+            sell_dollars(&mut pool, &mut trader, black_box(usd!(12.5)))
+        })
+    });
+    group.bench_function("sell_dollars from JSON", |b| {
+        let json = r#"{"sell_dollars":{"trader":"alice","dollars":"12.5USD"}}"#;
+        b.iter(|| {
+            let Ok(ServerRequest::SellDollars { dollars, .. }) =
+                serde_json::from_str(black_box(json))
+            else {
+                unreachable!()
+            };
+            let mut pool = Pool {
+                usd: usd!(103000),
+                euro: euro!(100000),
+            };
+            let mut trader = (usd!(1000).into_unsigned(), UnsignedAsset::zero(Euro));
+            sell_dollars(&mut pool, &mut trader, dollars)
+        })
+    });
+    group.bench_function("price", |b| {
+        b.iter(|| Price::<Usd, Euro>::from_asset_ratios(black_box(usd!(103000)), euro!(100000)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, swap);
+criterion_main!(benches);
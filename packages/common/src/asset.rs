@@ -1,29 +1,197 @@
-use std::{fmt::Display, marker::PhantomData, str::FromStr};
+use std::{fmt::Display, iter::Sum, marker::PhantomData, str::FromStr};
 
-use anyhow::{Context, Result};
-use numeric::{PositiveDecimal, UnsignedDecimal};
+use anyhow::Result;
+use numeric::{ParseMode, PositiveDecimal, SignedDecimal, UnsignedDecimal, DEFAULT_SCALE};
+
+use macros::Asset;
+
+use crate::{AssetDisplay, AssetFormat, NumericError};
 
 /// Any type that represents an asset type.
+///
+/// Define assets with `#[derive(Asset)]` on a unit struct. The `symbol` is
+/// required, `name`, `decimals` and `format` override the defaults below, and
+/// `alias` may be given more than once:
+///
+/// ```
+/// use common::{Asset, AssetFormat};
+///
+/// #[derive(Asset)]
+/// #[asset(symbol = "GBP", name = "Pound Sterling", decimals = 2, alias = "£")]
+/// #[asset(format = AssetFormat::FIAT)]
+/// pub struct Gbp;
+///
+/// assert_eq!(Gbp::display_name(), "Pound Sterling");
+/// assert_eq!("1.50£".parse::<common::PositiveAsset<Gbp>>().unwrap().to_string(), "1.5GBP");
+/// ```
+///
+/// The derive also implements the supertraits as well as [Clone] and [Copy],
+/// so don't derive those separately. Symbols and aliases must be unique
+/// within a crate, and reusing one is a compile error:
+///
+/// ```compile_fail
+/// #[derive(common::Asset)]
+/// #[asset(symbol = "GBP")]
+/// pub struct Gbp;
+///
+/// #[derive(common::Asset)]
+/// #[asset(symbol = "POUND", alias = "GBP")]
+/// pub struct Pound;
+/// ```
+///
+/// The generated code refers to this crate as `::common`. A crate which
+/// renames the dependency passes its path instead, e.g.
+/// `#[asset(crate = "::money")]`.
 pub trait Asset: Ord + std::fmt::Debug + Default {
+    /// The canonical symbol, used on the wire.
     fn as_str() -> &'static str;
+
+    /// A name for people to read, e.g. `US Dollar`. Defaults to the symbol.
+    fn display_name() -> &'static str {
+        Self::as_str()
+    }
+
+    /// Maximum number of fractional digits in an amount, at most
+    /// [DEFAULT_SCALE]. Defaults to [DEFAULT_SCALE].
+    ///
+    /// Parsing, decoding and the [asset!](crate::asset!) literals reject
+    /// amounts with more digits, but arithmetic such as
+    /// [UnsignedDecimal::mul_div] may still produce them, so round results
+    /// with [UnsignedDecimal::round_to] where needed.
+    ///
+    /// Assets with a finer minor unit than [DEFAULT_SCALE] allows, such as
+    /// satoshis, can't be represented.
+    const DECIMALS: u32 = DEFAULT_SCALE;
+
+    /// [Asset::DECIMALS], checked against [DEFAULT_SCALE] when the asset is
+    /// used. Override the constant rather than this method.
+    ///
+    /// ```compile_fail
+    /// #[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+    /// struct Sat;
+    ///
+    /// impl common::Asset for Sat {
+    ///     const DECIMALS: u32 = 8;
+    ///
+    ///     fn as_str() -> &'static str {
+    ///         "SAT"
+    ///     }
+    /// }
+    ///
+    /// use common::Asset;
+    /// Sat::decimals();
+    /// ```
+    fn decimals() -> u32 {
+        const {
+            assert!(
+                Self::DECIMALS <= DEFAULT_SCALE,
+                "Asset: decimals must be at most DEFAULT_SCALE"
+            )
+        };
+        Self::DECIMALS
+    }
+
+    /// Other symbols accepted when parsing, e.g. `$`.
+    fn aliases() -> &'static [&'static str] {
+        &[]
+    }
+
+    /// The smallest amount with [Asset::decimals], e.g. `0.01` for cents.
+    fn minor_unit() -> PositiveDecimal {
+        UnsignedDecimal::from_minor_units(1, Self::decimals())
+            .and_then(PositiveDecimal::new)
+            .expect("Asset: decimals is at most DEFAULT_SCALE")
+    }
+
+    /// How to render amounts of this asset for people to read.
+    fn format() -> AssetFormat {
+        AssetFormat::CANONICAL
+    }
 }
 
-macro_rules! make_asset {
-    ($i:ident, $name:expr) => {
-        #[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Default, Clone, Copy)]
-        pub struct $i;
-        impl Asset for $i {
-            fn as_str() -> &'static str {
-                $name
-            }
+#[derive(Asset)]
+#[asset(symbol = "USD", name = "US Dollar", decimals = 2, alias = "$")]
+#[asset(format = AssetFormat::FIAT)]
+pub struct Usd;
+
+#[derive(Asset)]
+#[asset(
+    symbol = "EURO",
+    name = "Euro",
+    decimals = 2,
+    alias = "€",
+    alias = "EUR"
+)]
+#[asset(format = AssetFormat::FIAT)]
+pub struct Euro;
+
+/// Bitcoin, in steps of 0.000001 BTC (100 satoshis).
+///
+/// Single satoshis would need 8 decimals, more than [DEFAULT_SCALE], so
+/// amounts such as `0.00000001BTC` are rejected, and minor units count
+/// steps of 100 satoshis rather than satoshis.
+#[derive(Asset)]
+#[asset(symbol = "BTC", name = "Bitcoin", decimals = 6, alias = "XBT")]
+pub struct Bitcoin;
+
+/// A [PositiveAsset] literal for the given asset type, checked at compile
+/// time, including against [Asset::DECIMALS].
+///
+/// ```
+/// use common::{asset, Euro, PositiveAsset};
+///
+/// const AMOUNT: PositiveAsset<Euro> = asset!(Euro, 12.5);
+/// assert_eq!(AMOUNT.to_string(), "12.5EURO");
+/// ```
+///
+/// ```compile_fail
+/// common::asset!(common::Euro, 0.001);
+/// ```
+#[macro_export]
+macro_rules! asset {
+    ($asset:ty, $($lit:tt)+) => {
+        const {
+            let value = $crate::__private::numeric::pdec!($($lit)+);
+            assert!(
+                value.get_unsigned().fraction_digits() <= <$asset as $crate::Asset>::DECIMALS,
+                "Asset literal has more fractional digits than the asset allows"
+            );
+            $crate::PositiveAsset::<$asset>::__from_decimal(value)
         }
     };
 }
 
-make_asset!(Usd, "USD");
-make_asset!(Euro, "EURO");
-// Not needed, just for fun
-make_asset!(Bitcoin, "BTC");
+/// A [PositiveAsset] of [Usd], checked at compile time.
+///
+/// ```
+/// assert_eq!(common::usd!(100.25).to_string(), "100.25USD");
+/// ```
+///
+/// ```compile_fail
+/// common::usd!(0);
+/// ```
+///
+/// ```compile_fail
+/// common::usd!(0.001);
+/// ```
+#[macro_export]
+macro_rules! usd {
+    ($($lit:tt)+) => {
+        $crate::asset!($crate::Usd, $($lit)+)
+    };
+}
+
+/// A [PositiveAsset] of [Euro], checked at compile time.
+///
+/// ```
+/// assert_eq!(common::euro!(10).to_string(), "10EURO");
+/// ```
+#[macro_export]
+macro_rules! euro {
+    ($($lit:tt)+) => {
+        $crate::asset!($crate::Euro, $($lit)+)
+    };
+}
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct PositiveAsset<I> {
@@ -47,8 +215,31 @@ impl<T> PositiveAsset<T> {
         }
     }
 
-    pub fn from_static(_: T, value: &'static str) -> PositiveAsset<T> {
-        let value = value.parse().unwrap();
+    /// Used by the [asset!](crate::asset!) macro.
+    #[doc(hidden)]
+    pub const fn __from_decimal(value: PositiveDecimal) -> PositiveAsset<T> {
+        PositiveAsset {
+            value,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn new_no_hints(value: PositiveDecimal) -> Self {
+        PositiveAsset {
+            value,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Panics if `value` is not a valid amount of `T`, including having more
+    /// fractional digits than [Asset::decimals]. Prefer
+    /// [asset!](crate::asset!), which checks the amount at compile time.
+    pub fn from_static(_: T, value: &'static str) -> PositiveAsset<T>
+    where
+        T: Asset,
+    {
+        let value: PositiveDecimal = value.parse().unwrap();
+        check_decimals::<T>(value.get_unsigned()).unwrap();
         PositiveAsset {
             value,
             _phantom: PhantomData,
@@ -63,7 +254,14 @@ impl<T> PositiveAsset<T> {
         UnsignedAsset::new_no_hints(self.value.get_unsigned())
     }
 
-    pub fn checked_sub(&self, rhs: PositiveAsset<T>) -> Result<Self> {
+    pub fn checked_add(&self, rhs: PositiveAsset<T>) -> Result<Self, NumericError> {
+        Ok(PositiveAsset {
+            value: self.value.checked_add(rhs.value)?,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn checked_sub(&self, rhs: PositiveAsset<T>) -> Result<Self, NumericError> {
         Ok(PositiveAsset {
             value: self.value.checked_sub(rhs.value)?,
             _phantom: PhantomData,
@@ -102,27 +300,39 @@ impl<T: Asset> serde::de::Visitor<'_> for PositiveAssetVisitor<T> {
     where
         E: serde::de::Error,
     {
-        v.parse().map_err(E::custom)
+        Self::Value::parse_with(v, ParseMode::Strict).map_err(crate::de::custom)
     }
 }
 
+/// Renders with [AssetFormat::CANONICAL], honoring the formatter's options,
+/// see [AssetDisplay].
 impl<T: Asset> Display for PositiveAsset<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}{}", self.value, T::as_str())
+        self.display_with(AssetFormat::CANONICAL).fmt(f)
     }
 }
 
-impl<T: Asset> FromStr for PositiveAsset<T> {
-    type Err = anyhow::Error;
+impl<T: Asset> PositiveAsset<T> {
+    /// Render using the asset's own format, see [Asset::format].
+    pub fn display(&self) -> AssetDisplay<'static> {
+        self.display_with(T::format())
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (value, asset) = split_amount_asset(s)?;
-        anyhow::ensure!(
-            asset == T::as_str(),
-            "Unexpected asset string {asset} found, expected {}",
-            T::as_str()
-        );
-        let value = value.parse()?;
+    pub fn display_with(&self, format: AssetFormat) -> AssetDisplay<'static> {
+        AssetDisplay {
+            amount: self.value.get_unsigned().into(),
+            symbol: T::as_str(),
+            format,
+        }
+    }
+}
+
+impl<T: Asset> PositiveAsset<T> {
+    /// Parse an amount followed by the asset symbol, using the given grammar
+    /// for the amount.
+    pub fn parse_with(s: &str, mode: ParseMode) -> Result<Self> {
+        let value = parse_amount::<T>(s, mode)?;
+        let value = PositiveDecimal::new(value)?;
         Ok(PositiveAsset {
             value,
             _phantom: PhantomData,
@@ -130,11 +340,68 @@ impl<T: Asset> FromStr for PositiveAsset<T> {
     }
 }
 
-pub(crate) fn split_amount_asset(s: &str) -> Result<(&str, &str)> {
-    let idx = s
-        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
-        .context("No asset type found")?;
-    Ok(s.split_at(idx))
+/// Parses with [ParseMode::Lenient].
+impl<T: Asset> FromStr for PositiveAsset<T> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, ParseMode::Lenient)
+    }
+}
+
+impl<T: Asset> PositiveAsset<T> {
+    /// The amount as an integer number of minor units, e.g. cents, failing
+    /// with [NumericError::PrecisionLoss] if it is not a whole number of
+    /// them.
+    pub fn to_minor_units(&self) -> Result<u128, NumericError> {
+        self.value.get_unsigned().to_minor_units(T::decimals())
+    }
+
+    /// Build an amount from minor units, e.g. cents, failing with
+    /// [NumericError::Zero] for 0.
+    pub fn from_minor_units(units: u128) -> Result<Self, NumericError> {
+        UnsignedDecimal::from_minor_units(units, T::decimals())
+            .and_then(PositiveDecimal::new)
+            .map(Self::new_no_hints)
+    }
+}
+
+pub(crate) fn split_amount_asset(s: &str, mode: ParseMode) -> Result<(&str, &str)> {
+    let (amount, asset) = numeric::parse::split_decimal(s, mode);
+    anyhow::ensure!(!asset.is_empty(), "No asset type found");
+    Ok((amount, asset))
+}
+
+/// Parse an amount of `T`, accepting its symbol or an alias, and rejecting
+/// more fractional digits than [Asset::decimals].
+fn parse_amount<T: Asset>(s: &str, mode: ParseMode) -> Result<UnsignedDecimal> {
+    let value = UnsignedDecimal::parse_with(strip_symbol::<T>(s, mode)?, mode)?;
+    check_decimals::<T>(value)?;
+    Ok(value)
+}
+
+/// The amount part of `s`, checking that the symbol is `T`'s symbol or an
+/// alias.
+fn strip_symbol<T: Asset>(s: &str, mode: ParseMode) -> Result<&str> {
+    // Match the symbol at the end first, so that a malformed amount such as
+    // 1.2.3USD is reported as a number error rather than an unknown symbol.
+    let symbols = std::iter::once(T::as_str()).chain(T::aliases().iter().copied());
+    if let Some(value) = symbols.filter_map(|symbol| s.strip_suffix(symbol)).next() {
+        return Ok(value);
+    }
+    let (_, asset) = split_amount_asset(s, mode)?;
+    anyhow::bail!(
+        "Unexpected asset string {asset} found, expected {}",
+        T::as_str()
+    )
+}
+
+pub(crate) fn check_decimals<T: Asset>(value: UnsignedDecimal) -> Result<(), NumericError> {
+    if value.fraction_digits() > T::decimals() {
+        Err(NumericError::PrecisionLoss)
+    } else {
+        Ok(())
+    }
 }
 
 impl<T: Asset> std::ops::AddAssign for PositiveAsset<T> {
@@ -175,7 +442,23 @@ impl<T> UnsignedAsset<T> {
         self.value
     }
 
-    pub fn checked_sub_assign(&mut self, rhs: Self) -> Result<()> {
+    pub(crate) fn get_value(&self) -> UnsignedDecimal {
+        self.value
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Result<Self, NumericError> {
+        self.value.checked_add(rhs.value).map(Self::new_no_hints)
+    }
+
+    pub fn checked_add_assign(&mut self, rhs: Self) -> Result<(), NumericError> {
+        self.value.checked_add_assign(rhs.into_decimal())
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, NumericError> {
+        self.value.checked_sub(rhs.value).map(Self::new_no_hints)
+    }
+
+    pub fn checked_sub_assign(&mut self, rhs: Self) -> Result<(), NumericError> {
         self.value.checked_sub_assign(rhs.into_decimal())
     }
 }
@@ -211,27 +494,38 @@ impl<T: Asset> serde::de::Visitor<'_> for UnsignedAssetVisitor<T> {
     where
         E: serde::de::Error,
     {
-        v.parse().map_err(E::custom)
+        Self::Value::parse_with(v, ParseMode::Strict).map_err(crate::de::custom)
     }
 }
 
+/// Renders with [AssetFormat::CANONICAL], honoring the formatter's options,
+/// see [AssetDisplay].
 impl<T: Asset> Display for UnsignedAsset<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}{}", self.value, T::as_str())
+        self.display_with(AssetFormat::CANONICAL).fmt(f)
     }
 }
 
-impl<T: Asset> FromStr for UnsignedAsset<T> {
-    type Err = anyhow::Error;
+impl<T: Asset> UnsignedAsset<T> {
+    /// Render using the asset's own format, see [Asset::format].
+    pub fn display(&self) -> AssetDisplay<'static> {
+        self.display_with(T::format())
+    }
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (value, asset) = split_amount_asset(s)?;
-        anyhow::ensure!(
-            asset == T::as_str(),
-            "Unexpected asset string {asset} found, expected {}",
-            T::as_str()
-        );
-        let value = value.parse()?;
+    pub fn display_with(&self, format: AssetFormat) -> AssetDisplay<'static> {
+        AssetDisplay {
+            amount: self.value.into(),
+            symbol: T::as_str(),
+            format,
+        }
+    }
+}
+
+impl<T: Asset> UnsignedAsset<T> {
+    /// Parse an amount followed by the asset symbol, using the given grammar
+    /// for the amount.
+    pub fn parse_with(s: &str, mode: ParseMode) -> Result<Self> {
+        let value = parse_amount::<T>(s, mode)?;
         Ok(UnsignedAsset {
             value,
             _phantom: PhantomData,
@@ -239,43 +533,386 @@ impl<T: Asset> FromStr for UnsignedAsset<T> {
     }
 }
 
+/// Parses with [ParseMode::Lenient].
+impl<T: Asset> FromStr for UnsignedAsset<T> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, ParseMode::Lenient)
+    }
+}
+
+impl<T: Asset> UnsignedAsset<T> {
+    /// The amount as an integer number of minor units, e.g. cents, failing
+    /// with [NumericError::PrecisionLoss] if it is not a whole number of
+    /// them.
+    pub fn to_minor_units(&self) -> Result<u128, NumericError> {
+        self.value.to_minor_units(T::decimals())
+    }
+
+    /// Build an amount from minor units, e.g. cents.
+    pub fn from_minor_units(units: u128) -> Result<Self, NumericError> {
+        UnsignedDecimal::from_minor_units(units, T::decimals()).map(Self::new_no_hints)
+    }
+}
+
 impl<T: Asset> std::ops::AddAssign for UnsignedAsset<T> {
     fn add_assign(&mut self, rhs: Self) {
         self.value += rhs.value;
     }
 }
 
+impl<T> From<PositiveAsset<T>> for UnsignedAsset<T> {
+    fn from(value: PositiveAsset<T>) -> Self {
+        value.into_unsigned()
+    }
+}
+
+/// Panics on overflow, see [UnsignedAsset::try_sum] for a checked version.
+impl<T> Sum for UnsignedAsset<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self::new_no_hints(iter.map(|x| x.value).sum())
+    }
+}
+
+impl<'a, T> Sum<&'a UnsignedAsset<T>> for UnsignedAsset<T> {
+    fn sum<I: Iterator<Item = &'a UnsignedAsset<T>>>(iter: I) -> Self {
+        Self::new_no_hints(iter.map(|x| x.value).sum())
+    }
+}
+
+/// Panics on overflow, see [UnsignedAsset::try_sum] for a checked version.
+impl<T> Sum<PositiveAsset<T>> for UnsignedAsset<T> {
+    fn sum<I: Iterator<Item = PositiveAsset<T>>>(iter: I) -> Self {
+        Self::new_no_hints(iter.map(|x| x.value).sum())
+    }
+}
+
+impl<'a, T> Sum<&'a PositiveAsset<T>> for UnsignedAsset<T> {
+    fn sum<I: Iterator<Item = &'a PositiveAsset<T>>>(iter: I) -> Self {
+        Self::new_no_hints(iter.map(|x| x.value).sum())
+    }
+}
+
+impl<T> UnsignedAsset<T> {
+    /// Add up amounts of the same asset, failing instead of panicking on
+    /// overflow. Accepts both unsigned and positive amounts.
+    pub fn try_sum<I>(iter: I) -> Result<Self, NumericError>
+    where
+        I: IntoIterator,
+        I::Item: Into<Self>,
+    {
+        let values = iter.into_iter().map(|x| Into::<Self>::into(x).value);
+        UnsignedDecimal::try_sum(values).map(Self::new_no_hints)
+    }
+}
+
+/// A signed amount of an asset, e.g. a balance change or a profit or loss.
+///
+/// The difference of two [UnsignedAsset]s is a [SignedAsset], and a
+/// [SignedAsset] can be applied to an [UnsignedAsset] balance with
+/// [UnsignedAsset::checked_add_signed]:
+///
+/// ```
+/// use common::{usd, SignedAsset, Usd};
+///
+/// let before = usd!(10).into_unsigned();
+/// let after = usd!(4.8).into_unsigned();
+/// let change = after - before;
+/// assert_eq!(change.to_string(), "-5.2USD");
+/// assert_eq!(change, "-5.2USD".parse::<SignedAsset<Usd>>().unwrap());
+/// assert_eq!(before.checked_add_signed(change).unwrap(), after);
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default, Clone, Copy)]
+pub struct SignedAsset<T> {
+    value: SignedDecimal,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> SignedAsset<T> {
+    pub fn new(_: T, value: SignedDecimal) -> Self {
+        Self::new_no_hints(value)
+    }
+
+    pub(crate) fn new_no_hints(value: SignedDecimal) -> Self {
+        SignedAsset {
+            value,
+            _phantom: PhantomData,
+        }
+    }
+
+    pub fn zero(_: T) -> Self {
+        Self::new_no_hints(SignedDecimal::zero())
+    }
+
+    pub fn into_decimal(self) -> SignedDecimal {
+        self.value
+    }
+
+    pub(crate) fn get_value(&self) -> SignedDecimal {
+        self.value
+    }
+
+    /// Is this amount strictly less than 0?
+    pub fn is_negative(&self) -> bool {
+        self.value.is_negative()
+    }
+
+    /// Is this amount strictly greater than 0?
+    pub fn is_positive(&self) -> bool {
+        self.value.is_positive()
+    }
+
+    pub fn negate(self) -> Self {
+        Self::new_no_hints(self.value.negate())
+    }
+
+    /// The magnitude, which never overflows.
+    pub fn unsigned_abs(self) -> UnsignedAsset<T> {
+        UnsignedAsset::new_no_hints(self.value.unsigned_abs())
+    }
+
+    /// Accepts signed, unsigned and positive amounts.
+    pub fn checked_add(self, rhs: impl Into<Self>) -> Result<Self, NumericError> {
+        self.value
+            .checked_add(rhs.into().value)
+            .map(Self::new_no_hints)
+    }
+
+    /// Accepts signed, unsigned and positive amounts.
+    pub fn checked_sub(self, rhs: impl Into<Self>) -> Result<Self, NumericError> {
+        self.value
+            .checked_sub(rhs.into().value)
+            .map(Self::new_no_hints)
+    }
+}
+
+impl<T: Asset> serde::Serialize for SignedAsset<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de, T: Asset> serde::Deserialize<'de> for SignedAsset<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(SignedAssetVisitor(PhantomData))
+    }
+}
+
+struct SignedAssetVisitor<T>(PhantomData<T>);
+
+impl<T: Asset> serde::de::Visitor<'_> for SignedAssetVisitor<T> {
+    type Value = SignedAsset<T>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Signed asset {}", T::as_str())
+    }
+
+    fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Self::Value::parse_with(v, ParseMode::Strict).map_err(crate::de::custom)
+    }
+}
+
+/// Renders with [AssetFormat::CANONICAL], honoring the formatter's options,
+/// see [AssetDisplay].
+impl<T: Asset> Display for SignedAsset<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.display_with(AssetFormat::CANONICAL).fmt(f)
+    }
+}
+
+impl<T: Asset> SignedAsset<T> {
+    /// Render using the asset's own format, see [Asset::format].
+    pub fn display(&self) -> AssetDisplay<'static> {
+        self.display_with(T::format())
+    }
+
+    pub fn display_with(&self, format: AssetFormat) -> AssetDisplay<'static> {
+        AssetDisplay {
+            amount: self.value,
+            symbol: T::as_str(),
+            format,
+        }
+    }
+}
+
+impl<T: Asset> SignedAsset<T> {
+    /// Parse an amount followed by the asset symbol, using the given grammar
+    /// for the amount.
+    pub fn parse_with(s: &str, mode: ParseMode) -> Result<Self> {
+        let value = SignedDecimal::parse_with(strip_symbol::<T>(s, mode)?, mode)?;
+        check_decimals::<T>(value.unsigned_abs())?;
+        Ok(Self::new_no_hints(value))
+    }
+}
+
+/// Parses with [ParseMode::Lenient].
+impl<T: Asset> FromStr for SignedAsset<T> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, ParseMode::Lenient)
+    }
+}
+
+/// Panics on overflow, see [SignedAsset::checked_add].
+impl<T> std::ops::AddAssign for SignedAsset<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.value += rhs.value;
+    }
+}
+
+/// Panics on overflow, see [SignedAsset::checked_sub].
+impl<T> std::ops::SubAssign for SignedAsset<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.value -= rhs.value;
+    }
+}
+
+impl<T> std::ops::Neg for SignedAsset<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.negate()
+    }
+}
+
+impl<T> From<UnsignedAsset<T>> for SignedAsset<T> {
+    fn from(value: UnsignedAsset<T>) -> Self {
+        Self::new_no_hints(value.value.into())
+    }
+}
+
+impl<T> From<PositiveAsset<T>> for SignedAsset<T> {
+    fn from(value: PositiveAsset<T>) -> Self {
+        value.into_unsigned().into()
+    }
+}
+
+/// Fails with [NumericError::Underflow] for negative amounts.
+impl<T> TryFrom<SignedAsset<T>> for UnsignedAsset<T> {
+    type Error = NumericError;
+
+    fn try_from(value: SignedAsset<T>) -> Result<Self, Self::Error> {
+        if value.is_negative() {
+            Err(NumericError::Underflow)
+        } else {
+            Ok(value.unsigned_abs())
+        }
+    }
+}
+
+/// Subtracting two unsigned amounts gives a signed difference, which can
+/// never overflow.
+impl<T> std::ops::Sub for UnsignedAsset<T> {
+    type Output = SignedAsset<T>;
+
+    fn sub(self, rhs: Self) -> SignedAsset<T> {
+        SignedAsset::new_no_hints(self.value - rhs.value)
+    }
+}
+
+/// Like subtracting unsigned amounts, this can never overflow.
+impl<T> std::ops::Sub for PositiveAsset<T> {
+    type Output = SignedAsset<T>;
+
+    fn sub(self, rhs: Self) -> SignedAsset<T> {
+        self.into_unsigned() - rhs.into_unsigned()
+    }
+}
+
+impl<T> UnsignedAsset<T> {
+    /// Apply a balance change, failing with [NumericError::Underflow] if the
+    /// result would be negative.
+    pub fn checked_add_signed(self, rhs: SignedAsset<T>) -> Result<Self, NumericError> {
+        if rhs.is_negative() {
+            self.checked_sub(rhs.unsigned_abs())
+        } else {
+            self.checked_add(rhs.unsigned_abs())
+        }
+    }
+}
+
+/// Panics on overflow, see [SignedAsset::try_sum] for a checked version.
+impl<T> Sum for SignedAsset<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new_no_hints(SignedDecimal::zero()), |mut acc, x| {
+            acc += x;
+            acc
+        })
+    }
+}
+
+impl<T> SignedAsset<T> {
+    /// Add up amounts of the same asset, failing instead of panicking on
+    /// overflow. Accepts signed, unsigned and positive amounts.
+    pub fn try_sum<I>(iter: I) -> Result<Self, NumericError>
+    where
+        I: IntoIterator,
+        I::Item: Into<Self>,
+    {
+        iter.into_iter()
+            .try_fold(Self::new_no_hints(SignedDecimal::zero()), |acc, x| {
+                acc.checked_add(x)
+            })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
-    use asset::split_amount_asset;
+    use asset::{split_amount_asset, Bitcoin};
 
     use crate::*;
 
     #[test]
     fn split_amount() {
-        assert_eq!(split_amount_asset("5USD").unwrap(), ("5", "USD"));
-        assert_eq!(split_amount_asset("5.0USD").unwrap(), ("5.0", "USD"));
-        split_amount_asset("25").unwrap_err();
-        split_amount_asset("25.0").unwrap_err();
-        split_amount_asset("25.").unwrap_err();
+        let split = |s| split_amount_asset(s, ParseMode::Lenient);
+        assert_eq!(split("5USD").unwrap(), ("5", "USD"));
+        assert_eq!(split("5.0USD").unwrap(), ("5.0", "USD"));
+        assert_eq!(split("1_000.5EURO").unwrap(), ("1_000.5", "EURO"));
+        assert_eq!(split("1e3EURO").unwrap(), ("1e3", "EURO"));
+        split("25").unwrap_err();
+        split("25.0").unwrap_err();
+        split("25.").unwrap_err();
+    }
+
+    #[test]
+    fn parse_modes() {
+        let lenient: PositiveAsset<Euro> = "+1_000.5EURO".parse().unwrap();
+        assert_eq!(lenient, euro!(1000.5));
+        PositiveAsset::<Euro>::parse_with("+1_000.5EURO", ParseMode::Strict).unwrap_err();
+        assert_eq!(
+            UnsignedAsset::<Usd>::parse_with("0.5USD", ParseMode::Strict).unwrap(),
+            usd!(0.5).into_unsigned()
+        );
+        serde_json::from_str::<PositiveAsset<Usd>>("\"1e3USD\"").unwrap_err();
     }
 
     #[test]
     fn positive_asset_render_and_parse() {
-        let usd = PositiveAsset::from_static(Usd, "123.456");
+        let usd = usd!(123.45);
         let s = usd.to_string();
-        assert_eq!(s, "123.456USD");
+        assert_eq!(s, "123.45USD");
         let usd2: PositiveAsset<Usd> = s.parse().unwrap();
         assert_eq!(usd, usd2);
     }
 
     #[test]
     fn positive_asset_serde() {
-        let usd = PositiveAsset::from_static(Usd, "123.456");
+        let usd = usd!(123.45);
         let s = serde_json::to_string(&usd).unwrap();
-        assert_eq!(s, r#""123.456USD""#);
+        assert_eq!(s, r#""123.45USD""#);
         let usd2: PositiveAsset<Usd> = serde_json::from_str(&s).unwrap();
         assert_eq!(usd, usd2);
     }
@@ -287,6 +924,124 @@ mod tests {
         serde_json::from_str::<PositiveAsset<Usd>>("\"0USD\"").unwrap_err();
     }
 
+    #[test]
+    fn derive_options() {
+        // Deriving inside a function is allowed, and the path to this crate
+        // can be overridden.
+        #[derive(Asset)]
+        #[asset(symbol = "LOCAL", crate = "crate")]
+        struct Local;
+
+        assert_eq!(Local::as_str(), "LOCAL");
+        assert_eq!(Local::decimals(), numeric::DEFAULT_SCALE);
+    }
+
+    #[test]
+    fn asset_metadata() {
+        assert_eq!(Usd::display_name(), "US Dollar");
+        assert_eq!(Usd::decimals(), 2);
+        assert_eq!(Usd::minor_unit(), pdec!(0.01));
+        assert_eq!(Bitcoin::minor_unit(), pdec!(0.000001));
+        assert_eq!(Euro::aliases(), ["€", "EUR"]);
+        assert_eq!(Bitcoin::format(), AssetFormat::CANONICAL);
+        assert_eq!(format!("{Usd:?}"), "Usd");
+        assert_eq!(Usd.cmp(&Usd.clone()), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn asset_precision() {
+        let err = UnsignedAsset::<Usd>::from_str("1.255USD").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<NumericError>(),
+            Some(&NumericError::PrecisionLoss)
+        );
+        serde_json::from_str::<PositiveAsset<Euro>>("\"0.001EURO\"").unwrap_err();
+        assert_eq!(
+            "1.250USD".parse::<PositiveAsset<Usd>>().unwrap(),
+            usd!(1.25)
+        );
+        assert_eq!("5€".parse::<PositiveAsset<Euro>>().unwrap(), euro!(5));
+        assert_eq!(
+            "0.000001XBT".parse::<PositiveAsset<Bitcoin>>().unwrap(),
+            asset!(Bitcoin, 0.000001)
+        );
+        assert_eq!(PositiveAsset::from_static(Usd, "1.25"), usd!(1.25));
+    }
+
+    #[test]
+    #[should_panic(expected = "PrecisionLoss")]
+    fn from_static_checks_decimals() {
+        PositiveAsset::from_static(Usd, "1.255");
+    }
+
+    #[test]
+    fn minor_units() {
+        assert_eq!(usd!(1.5).to_minor_units(), Ok(150));
+        assert_eq!(PositiveAsset::<Usd>::from_minor_units(150), Ok(usd!(1.5)));
+        assert_eq!(
+            PositiveAsset::<Usd>::from_minor_units(0),
+            Err(NumericError::Zero)
+        );
+        assert_eq!(
+            UnsignedAsset::<Euro>::from_minor_units(0),
+            Ok(UnsignedAsset::zero(Euro))
+        );
+        // Arithmetic can produce amounts finer than the asset's precision,
+        // which the literals reject.
+        assert_eq!(
+            PositiveAsset::new(Usd, pdec!(0.001)).to_minor_units(),
+            Err(NumericError::PrecisionLoss)
+        );
+        // Bitcoin's minor unit is 100 satoshis, and a single one is rejected.
+        assert_eq!(
+            PositiveAsset::<Bitcoin>::from_minor_units(1),
+            Ok(asset!(Bitcoin, 0.000001))
+        );
+        let err = "0.00000001BTC"
+            .parse::<PositiveAsset<Bitcoin>>()
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<NumericError>(),
+            Some(&NumericError::TooManyFractionDigits { max: 6 })
+        );
+    }
+
+    #[test]
+    fn checked_asset_arithmetic() {
+        let max = UnsignedAsset::new(Usd, UnsignedDecimal::MAX);
+        let one = UnsignedAsset::new(Usd, "1".parse().unwrap());
+        assert_eq!(max.checked_add(one), Err(NumericError::Overflow));
+        assert_eq!(one.checked_sub(max), Err(NumericError::Underflow));
+
+        let usd = usd!(5);
+        assert_eq!(usd.checked_sub(usd), Err(NumericError::Zero));
+        assert_eq!(usd.checked_add(usd).unwrap(), usd!(10));
+    }
+
+    #[test]
+    fn sum_assets() {
+        let balances = [usd!(1.5).into_unsigned(), UnsignedAsset::zero(Usd)];
+        assert_eq!(
+            balances.iter().sum::<UnsignedAsset<Usd>>(),
+            usd!(1.5).into_unsigned()
+        );
+        let payments = [usd!(1), usd!(2.25)];
+        assert_eq!(
+            payments.iter().sum::<UnsignedAsset<Usd>>(),
+            usd!(3.25).into_unsigned()
+        );
+        assert_eq!(
+            UnsignedAsset::try_sum(payments),
+            Ok(usd!(3.25).into_unsigned())
+        );
+
+        let max = UnsignedAsset::new(Usd, UnsignedDecimal::MAX);
+        assert_eq!(
+            UnsignedAsset::try_sum([max, usd!(1).into_unsigned()]),
+            Err(NumericError::Overflow)
+        );
+    }
+
     #[test]
     fn parse_negative_unsigned_asset() {
         UnsignedAsset::<Usd>::from_str("-5000USD").unwrap_err();
@@ -295,4 +1050,71 @@ mod tests {
             UnsignedAsset::new(Usd, "5000".parse().unwrap())
         )
     }
+
+    #[test]
+    fn signed_asset() {
+        let loss: SignedAsset<Usd> = "-5.2USD".parse().unwrap();
+        assert!(loss.is_negative());
+        assert_eq!(loss.to_string(), "-5.2USD");
+        assert_eq!(format!("{:+}", loss.negate()), "+5.2USD");
+        assert_eq!(loss.display().to_string(), "-5.20 USD");
+        assert_eq!(
+            serde_json::from_str::<SignedAsset<Usd>>("\"-5.2USD\"").unwrap(),
+            loss
+        );
+        assert_eq!(serde_json::to_string(&loss).unwrap(), "\"-5.2USD\"");
+        serde_json::from_str::<SignedAsset<Usd>>("\"-5.2EURO\"").unwrap_err();
+        serde_json::from_str::<SignedAsset<Usd>>("\"+5.2USD\"").unwrap_err();
+        SignedAsset::<Usd>::from_str("-0USD").unwrap_err();
+        let err = SignedAsset::<Usd>::from_str("-1.255$").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<NumericError>(),
+            Some(&NumericError::PrecisionLoss)
+        );
+    }
+
+    #[test]
+    fn signed_arithmetic() {
+        let balance = usd!(10).into_unsigned();
+        let change = usd!(4.8) - usd!(10);
+        assert_eq!(change, -SignedAsset::from(usd!(5.2)));
+        assert_eq!(usd!(4.8).into_unsigned() - balance, change);
+        assert_eq!(
+            balance.checked_add_signed(change).unwrap(),
+            usd!(4.8).into_unsigned()
+        );
+        assert_eq!(
+            balance.checked_add_signed(change.negate()).unwrap(),
+            usd!(15.2).into_unsigned()
+        );
+        assert_eq!(
+            UnsignedAsset::zero(Usd).checked_add_signed(change),
+            Err(NumericError::Underflow)
+        );
+
+        assert_eq!(
+            change.checked_add(usd!(5.2)).unwrap(),
+            SignedAsset::zero(Usd)
+        );
+        assert_eq!(change.checked_sub(balance).unwrap().to_string(), "-15.2USD");
+        assert_eq!(change.unsigned_abs(), usd!(5.2).into_unsigned());
+        assert_eq!(
+            UnsignedAsset::try_from(change),
+            Err(NumericError::Underflow)
+        );
+        assert_eq!(
+            UnsignedAsset::try_from(change.negate()),
+            Ok(usd!(5.2).into_unsigned())
+        );
+
+        let changes = [change, usd!(7).into(), balance.into()];
+        assert_eq!(
+            changes.into_iter().sum::<SignedAsset<Usd>>().to_string(),
+            "11.8USD"
+        );
+        assert_eq!(
+            SignedAsset::try_sum([usd!(1), usd!(2.5)]).unwrap(),
+            usd!(3.5).into()
+        );
+    }
 }
@@ -0,0 +1,256 @@
+//! Binary encoding for the asset types.
+//!
+//! Assets use the same encoding as their amount, see
+//! [numeric](numeric#binary-encoding). The asset type itself is not
+//! encoded, since it is part of the Rust type.
+//!
+//! Decoding fails with [NumericError::PrecisionLoss] for an amount with more
+//! fractional digits than [Asset::decimals], like parsing does.
+
+use numeric::{PositiveDecimal, SignedDecimal, UnsignedDecimal};
+
+use crate::{
+    asset::check_decimals, Asset, NumericError, PositiveAsset, SignedAsset, UnsignedAsset,
+};
+
+impl<T> PositiveAsset<T> {
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.get_value().to_bytes()
+    }
+}
+
+impl<T: Asset> PositiveAsset<T> {
+    /// Fails with [NumericError::Zero] for 0.
+    pub fn from_bytes(bytes: [u8; 16]) -> Result<Self, NumericError> {
+        let value = PositiveDecimal::from_bytes(bytes)?;
+        check_decimals::<T>(value.get_unsigned())?;
+        Ok(Self::new_no_hints(value))
+    }
+}
+
+impl<T> UnsignedAsset<T> {
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.get_value().to_bytes()
+    }
+}
+
+impl<T: Asset> UnsignedAsset<T> {
+    pub fn from_bytes(bytes: [u8; 16]) -> Result<Self, NumericError> {
+        let value = UnsignedDecimal::from_bytes(bytes);
+        check_decimals::<T>(value)?;
+        Ok(Self::new_no_hints(value))
+    }
+}
+
+impl<T> SignedAsset<T> {
+    pub fn to_bytes(&self) -> [u8; 17] {
+        self.get_value().to_bytes()
+    }
+}
+
+impl<T: Asset> SignedAsset<T> {
+    /// Fails with [NumericError::NegativeZero] for a negative 0 and
+    /// [NumericError::InvalidEncoding] for an invalid sign byte.
+    pub fn from_bytes(bytes: [u8; 17]) -> Result<Self, NumericError> {
+        let value = SignedDecimal::from_bytes(bytes)?;
+        check_decimals::<T>(value.unsigned_abs())?;
+        Ok(Self::new_no_hints(value))
+    }
+}
+
+#[cfg(feature = "borsh")]
+mod borsh {
+    use ::borsh::{
+        io::{Error, ErrorKind, Read, Result, Write},
+        BorshDeserialize, BorshSerialize,
+    };
+
+    use super::*;
+
+    impl<T> BorshSerialize for PositiveAsset<T> {
+        fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+            writer.write_all(&self.to_bytes())
+        }
+    }
+
+    impl<T: Asset> BorshDeserialize for PositiveAsset<T> {
+        fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+            Self::from_bytes(<[u8; 16]>::deserialize_reader(reader)?)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+        }
+    }
+
+    impl<T> BorshSerialize for UnsignedAsset<T> {
+        fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+            writer.write_all(&self.to_bytes())
+        }
+    }
+
+    impl<T: Asset> BorshDeserialize for UnsignedAsset<T> {
+        fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+            Self::from_bytes(<[u8; 16]>::deserialize_reader(reader)?)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+        }
+    }
+
+    impl<T> BorshSerialize for SignedAsset<T> {
+        fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+            writer.write_all(&self.to_bytes())
+        }
+    }
+
+    impl<T: Asset> BorshDeserialize for SignedAsset<T> {
+        fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+            Self::from_bytes(<[u8; 17]>::deserialize_reader(reader)?)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+        }
+    }
+}
+
+#[cfg(feature = "bincode")]
+mod bincode {
+    use ::bincode::{
+        de::{BorrowDecoder, Decoder},
+        enc::Encoder,
+        error::{DecodeError, EncodeError},
+        BorrowDecode, Decode, Encode,
+    };
+
+    use super::*;
+
+    fn decode_error(e: NumericError) -> DecodeError {
+        DecodeError::Other(match e {
+            NumericError::Zero => "a positive asset cannot be 0",
+            NumericError::NegativeZero => "a signed asset cannot be negative 0",
+            NumericError::PrecisionLoss => "too many fractional digits for the asset",
+            _ => "invalid asset encoding",
+        })
+    }
+
+    impl<T> Encode for PositiveAsset<T> {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            self.get_value().encode(encoder)
+        }
+    }
+
+    impl<T: Asset> Decode for PositiveAsset<T> {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Self::from_bytes(<[u8; 16]>::decode(decoder)?).map_err(decode_error)
+        }
+    }
+
+    impl<'de, T: Asset> BorrowDecode<'de> for PositiveAsset<T> {
+        fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Self::decode(decoder)
+        }
+    }
+
+    impl<T> Encode for UnsignedAsset<T> {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            self.get_value().encode(encoder)
+        }
+    }
+
+    impl<T: Asset> Decode for UnsignedAsset<T> {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Self::from_bytes(<[u8; 16]>::decode(decoder)?).map_err(decode_error)
+        }
+    }
+
+    impl<'de, T: Asset> BorrowDecode<'de> for UnsignedAsset<T> {
+        fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Self::decode(decoder)
+        }
+    }
+
+    impl<T> Encode for SignedAsset<T> {
+        fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+            self.get_value().encode(encoder)
+        }
+    }
+
+    impl<T: Asset> Decode for SignedAsset<T> {
+        fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Self::from_bytes(<[u8; 17]>::decode(decoder)?).map_err(decode_error)
+        }
+    }
+
+    impl<'de, T: Asset> BorrowDecode<'de> for SignedAsset<T> {
+        fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+            Self::decode(decoder)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn asset_bytes() {
+        let usd = usd!(1.5);
+        assert_eq!(usd.to_bytes(), dec!(1.5).to_bytes());
+        assert_eq!(PositiveAsset::from_bytes(usd.to_bytes()), Ok(usd));
+        assert_eq!(
+            PositiveAsset::<Usd>::from_bytes([0; 16]),
+            Err(NumericError::Zero)
+        );
+        let zero = UnsignedAsset::zero(Euro);
+        assert_eq!(UnsignedAsset::from_bytes(zero.to_bytes()), Ok(zero));
+        let loss = -SignedAsset::from(usd);
+        assert_eq!(loss.to_bytes(), loss.into_decimal().to_bytes());
+        assert_eq!(SignedAsset::from_bytes(loss.to_bytes()), Ok(loss));
+
+        // Finer than the 2 decimals of USD
+        let fine = dec!(1.255).to_bytes();
+        assert_eq!(
+            PositiveAsset::<Usd>::from_bytes(fine),
+            Err(NumericError::PrecisionLoss)
+        );
+        assert_eq!(
+            UnsignedAsset::<Usd>::from_bytes(fine),
+            Err(NumericError::PrecisionLoss)
+        );
+        let fine_loss = SignedDecimal::from(dec!(1.255)).negate().to_bytes();
+        assert_eq!(
+            SignedAsset::<Usd>::from_bytes(fine_loss),
+            Err(NumericError::PrecisionLoss)
+        );
+        assert!(UnsignedAsset::<Usd>::from_bytes(dec!(1.25).to_bytes()).is_ok());
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn asset_borsh() {
+        let usd = usd!(1.5);
+        let bytes = borsh::to_vec(&usd).unwrap();
+        assert_eq!(bytes, usd.to_bytes());
+        assert_eq!(
+            borsh::from_slice::<PositiveAsset<Usd>>(&bytes).unwrap(),
+            usd
+        );
+        borsh::from_slice::<PositiveAsset<Usd>>(&[0; 16]).unwrap_err();
+        let mut negative_zero = [0; 17];
+        negative_zero[16] = 1;
+        borsh::from_slice::<SignedAsset<Usd>>(&negative_zero).unwrap_err();
+        borsh::from_slice::<UnsignedAsset<Usd>>(&dec!(1.255).to_bytes()).unwrap_err();
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn asset_bincode() {
+        let config = bincode::config::standard();
+        let euro = euro!(2).into_unsigned();
+        let bytes = bincode::encode_to_vec(euro, config).unwrap();
+        assert_eq!(bytes, euro.to_bytes());
+        let (decoded, _) = bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(euro, decoded);
+        let loss = UnsignedAsset::zero(Euro) - euro;
+        let bytes = bincode::encode_to_vec(loss, config).unwrap();
+        assert_eq!(bytes, loss.to_bytes());
+        let (decoded, _) = bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(loss, decoded);
+        let fine = dec!(1.255).to_bytes();
+        bincode::decode_from_slice::<UnsignedAsset<Usd>, _>(&fine, config).unwrap_err();
+    }
+}
@@ -0,0 +1,92 @@
+//! Recovering the [NumericError] behind a deserialization failure.
+//!
+//! [serde::de::Error::custom] only keeps the message, so the visitors in
+//! this crate record the typed error on the side, and [with_numeric_errors]
+//! puts it back into the error chain.
+
+use std::cell::Cell;
+
+use crate::NumericError;
+
+thread_local! {
+    static LAST_ERROR: Cell<Option<NumericError>> = const { Cell::new(None) };
+}
+
+/// Convert a parse error into a deserialization error, recording the
+/// [NumericError] that caused it, if any.
+pub(crate) fn custom<E: serde::de::Error>(e: anyhow::Error) -> E {
+    if let Some(numeric) = e.chain().find_map(|e| e.downcast_ref::<NumericError>()) {
+        LAST_ERROR.set(Some(*numeric));
+    }
+    E::custom(e)
+}
+
+/// Run a deserializer such as `serde_json::from_slice`, keeping the
+/// [NumericError] in the error chain when parsing one of this crate's types
+/// failed because of it, so that it can be found with
+/// [anyhow::Error::downcast_ref].
+///
+/// ```
+/// use common::{NumericError, ServerRequest};
+///
+/// let json = r#"{"sell_dollars":{"trader":"alice","dollars":"1.255USD"}}"#;
+/// let Err(e) = common::with_numeric_errors(|| serde_json::from_str::<ServerRequest>(json))
+/// else {
+///     panic!("1.255USD has too many decimals");
+/// };
+/// assert_eq!(
+///     e.chain().find_map(|e| e.downcast_ref::<NumericError>()),
+///     Some(&NumericError::PrecisionLoss)
+/// );
+/// ```
+pub fn with_numeric_errors<T, E>(f: impl FnOnce() -> Result<T, E>) -> anyhow::Result<T>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    LAST_ERROR.set(None);
+    let result = f();
+    let numeric = LAST_ERROR.take();
+    result.map_err(|e| match numeric {
+        // The deserializer's message comes first, since it includes the
+        // position in the input.
+        Some(numeric) => anyhow::Error::new(numeric).context(e.to_string()),
+        None => e.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn numeric_error(json: &str) -> Option<NumericError> {
+        let Err(e) = with_numeric_errors(|| serde_json::from_str::<ServerRequest>(json)) else {
+            panic!("{json} should not parse");
+        };
+        e.chain()
+            .find_map(|e| e.downcast_ref::<NumericError>())
+            .copied()
+    }
+
+    #[test]
+    fn recover_numeric_errors() {
+        let sell = |dollars: &str| {
+            numeric_error(&format!(
+                r#"{{"sell_dollars":{{"trader":"alice","dollars":"{dollars}"}}}}"#
+            ))
+        };
+        assert_eq!(
+            sell("1.2.3USD"),
+            Some(NumericError::InvalidDigit { position: 3 })
+        );
+        assert_eq!(sell("1.255USD"), Some(NumericError::PrecisionLoss));
+        assert_eq!(sell("0USD"), Some(NumericError::Zero));
+        assert_eq!(sell("USD"), Some(NumericError::Empty));
+        assert_eq!(sell("1EURO"), None);
+        // A later failure without a numeric cause does not pick up an
+        // earlier one.
+        assert_eq!(numeric_error(r#"{"status":{"#), None);
+
+        let ok = with_numeric_errors(|| serde_json::from_str::<ServerRequest>(r#"{"status":{}}"#));
+        assert!(ok.is_ok());
+    }
+}
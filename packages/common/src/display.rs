@@ -0,0 +1,122 @@
+use std::fmt::{Alignment, Display, Write};
+
+use numeric::{FormatOptions, SignedDecimal};
+
+/// Where to place the asset symbol relative to the amount.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SymbolPosition {
+    /// `USD 5`
+    Prefix,
+    /// `5 USD`
+    Suffix,
+}
+
+/// How to render an amount of an asset for people to read.
+///
+/// The [Display] impls on the asset types always use [AssetFormat::CANONICAL],
+/// which is also the wire format. Use [crate::PositiveAsset::display] for the
+/// asset's own format, or [crate::PositiveAsset::display_with] to pick one.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct AssetFormat {
+    pub symbol_position: SymbolPosition,
+    /// Put a space between the amount and the symbol.
+    pub space: bool,
+    /// Always render this many fractional digits, rounding half to even.
+    pub precision: Option<usize>,
+}
+
+impl AssetFormat {
+    /// The format used on the wire, e.g. `5.7USD`.
+    pub const CANONICAL: AssetFormat = AssetFormat {
+        symbol_position: SymbolPosition::Suffix,
+        space: false,
+        precision: None,
+    };
+
+    /// A typical format for fiat currencies, e.g. `5.70 USD`.
+    pub const FIAT: AssetFormat = AssetFormat {
+        symbol_position: SymbolPosition::Suffix,
+        space: true,
+        precision: Some(2),
+    };
+}
+
+impl Default for AssetFormat {
+    fn default() -> Self {
+        AssetFormat::CANONICAL
+    }
+}
+
+/// Renders an asset amount with an [AssetFormat].
+///
+/// Formatter options apply as well: precision overrides the format's
+/// precision, `+` and `#` are passed on to the amount, and width, fill and
+/// alignment apply to the whole rendered string.
+pub struct AssetDisplay<'a> {
+    pub(crate) amount: SignedDecimal,
+    pub(crate) symbol: &'a str,
+    pub(crate) format: AssetFormat,
+}
+
+impl Display for AssetDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let options = FormatOptions {
+            precision: f.precision().or(self.format.precision),
+            ..FormatOptions::from_formatter(f)
+        };
+        let amount = self.amount.display_with(options);
+        let space = if self.format.space { " " } else { "" };
+        let rendered = match self.format.symbol_position {
+            SymbolPosition::Prefix => format!("{}{space}{amount}", self.symbol),
+            SymbolPosition::Suffix => format!("{amount}{space}{}", self.symbol),
+        };
+
+        // Formatter::pad would treat the precision as a maximum width.
+        let padding = f
+            .width()
+            .unwrap_or(0)
+            .saturating_sub(rendered.chars().count());
+        let (before, after) = match f.align() {
+            Some(Alignment::Left) => (0, padding),
+            Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(Alignment::Right) | None => (padding, 0),
+        };
+        for _ in 0..before {
+            f.write_char(f.fill())?;
+        }
+        f.write_str(&rendered)?;
+        for _ in 0..after {
+            f.write_char(f.fill())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn asset_formats() {
+        let usd = usd!(1234.5);
+        assert_eq!(usd.to_string(), "1234.5USD");
+        assert_eq!(format!("{usd:.2}"), "1234.50USD");
+        assert_eq!(usd.display().to_string(), "1234.50 USD");
+        assert_eq!(format!("{:#}", usd.display()), "1,234.50 USD");
+        assert_eq!(format!("{:.0}", usd.display()), "1234 USD");
+        assert_eq!(format!("{:>14}|", usd.display()), "   1234.50 USD|");
+        assert_eq!(format!("{:<14}|", usd.display()), "1234.50 USD   |");
+
+        let prefix = AssetFormat {
+            symbol_position: SymbolPosition::Prefix,
+            space: true,
+            precision: Some(3),
+        };
+        assert_eq!(usd.display_with(prefix).to_string(), "USD 1234.500");
+        assert_eq!(
+            UnsignedAsset::zero(Euro).display_with(prefix).to_string(),
+            "EURO 0.000"
+        );
+        assert_eq!(UnsignedAsset::zero(Euro).display().to_string(), "0.00 EURO");
+    }
+}
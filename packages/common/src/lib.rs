@@ -1,11 +1,33 @@
+// Lets #[derive(Asset)] refer to ::common within this crate as well.
+extern crate self as common;
+
 mod asset;
+mod binary;
+mod de;
+mod display;
 mod messages;
+mod pool;
 mod price;
+mod registry;
 
-pub use asset::{Asset, Euro, PositiveAsset, UnsignedAsset, Usd};
+pub use asset::{Asset, Euro, PositiveAsset, SignedAsset, UnsignedAsset, Usd};
+pub use de::with_numeric_errors;
+pub use display::{AssetDisplay, AssetFormat, SymbolPosition};
+/// Derives [Asset](trait@Asset), see the trait for the attributes.
+pub use macros::Asset;
 pub use messages::{
     BalanceResp, ListOwnersResp, MintFundsResp, Owner, SellDollarsResp, SellEurosResp,
     ServerRequest, StatusResp,
 };
-pub use numeric::{PositiveDecimal, SignedDecimal, UnsignedDecimal};
-pub use price::Price;
+pub use numeric::{
+    dec, pdec, Error as NumericError, ParseMode, PositiveDecimal, Rational, RoundingMode,
+    SignedDecimal, UnsignedDecimal,
+};
+pub use pool::{swap, Swap};
+pub use price::{Price, PriceValue};
+pub use registry::{AnyAmount, AssetInfo, AssetRegistry, Denom};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use numeric;
+}
@@ -0,0 +1,80 @@
+//! The constant product swap calculation, shared by the server and the
+//! benches.
+
+use crate::{Asset, NumericError, PositiveAsset, PositiveDecimal, RoundingMode};
+
+/// The result of a [swap].
+#[derive(Debug, PartialEq, Eq)]
+pub struct Swap<Sold, Bought> {
+    /// The pool's new balance of the sold asset.
+    pub pool_sold: PositiveAsset<Sold>,
+    /// The pool's new balance of the bought asset.
+    pub pool_bought: PositiveAsset<Bought>,
+    /// The amount paid out to the trader.
+    pub bought: PositiveAsset<Bought>,
+}
+
+/// Sell `sold` into a pool holding `pool_sold` and `pool_bought`.
+///
+/// The pool has a constant, K, the product of both balances, which must
+/// remain the same. The new balance of the bought asset is K divided by the
+/// new balance of the sold asset, computed as a single fused operation so
+/// that K is never rounded, and cannot overflow, on its own.
+///
+/// Rounding always favours the pool: the new balance is rounded up, to
+/// [Asset::decimals] digits, so the trader never receives more than the exact
+/// calculation would give. Fails with [NumericError::Zero] if the trader
+/// would receive nothing.
+///
+/// Nothing is modified, so the caller can check every other part of the
+/// trade before applying the result.
+pub fn swap<Sold, Bought: Asset>(
+    pool_sold: PositiveAsset<Sold>,
+    pool_bought: PositiveAsset<Bought>,
+    sold: PositiveAsset<Sold>,
+) -> Result<Swap<Sold, Bought>, NumericError> {
+    let new_pool_sold = pool_sold.checked_add(sold)?;
+    let new_pool_bought = pool_sold
+        .into_unsigned()
+        .into_decimal()
+        .mul_div(
+            pool_bought.into_unsigned().into_decimal(),
+            new_pool_sold.into_unsigned().into_decimal(),
+            RoundingMode::Up,
+        )?
+        .round_to(Bought::decimals(), RoundingMode::Up)?;
+    let new_pool_bought = PositiveAsset::new_no_hints(PositiveDecimal::new(new_pool_bought)?);
+    let bought = pool_bought.checked_sub(new_pool_bought)?;
+    Ok(Swap {
+        pool_sold: new_pool_sold,
+        pool_bought: new_pool_bought,
+        bought,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn swap_rounds_in_favour_of_the_pool() {
+        // K / 103012.5 = 99987.8655..., rounded up to whole cents
+        assert_eq!(
+            swap(usd!(103000), euro!(100000), usd!(12.5)),
+            Ok(Swap {
+                pool_sold: usd!(103012.5),
+                pool_bought: euro!(99987.87),
+                bought: euro!(12.13),
+            })
+        );
+        assert_eq!(
+            swap(euro!(100000), usd!(103000), euro!(12.5)).map(|swap| swap.bought),
+            Ok(usd!(12.87))
+        );
+        // K / 103000.01 = 99999.9902..., which rounds up to the old balance
+        assert_eq!(
+            swap(usd!(103000), euro!(100000), usd!(0.01)),
+            Err(NumericError::Zero)
+        );
+    }
+}
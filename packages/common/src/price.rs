@@ -1,47 +1,242 @@
 use std::{fmt::Display, marker::PhantomData, str::FromStr};
 
 use anyhow::Context;
-use numeric::PositiveDecimal;
+use numeric::{parse, ParseMode, PositiveDecimal, Rational, RoundingMode, UnsignedDecimal};
 use serde::de::Visitor;
 
-use crate::{
-    asset::{split_amount_asset, PositiveAsset},
-    Asset,
-};
+use crate::{Asset, NumericError, PositiveAsset, UnsignedAsset};
 
 /// The price of the base asset in terms of the quote.
+///
+/// By default the price is stored as a [PositiveDecimal], which truncates to
+/// 6 fractional digits. Use `Price<Base, Quote, Rational>` to keep prices
+/// exact, see [PriceValue].
 #[derive(PartialEq, Eq, Debug, Clone, Copy)]
-pub struct Price<Base, Quote> {
-    price: PositiveDecimal,
+pub struct Price<Base, Quote, Value = PositiveDecimal> {
+    price: Value,
     _base: PhantomData<Base>,
     _quote: PhantomData<Quote>,
 }
 
-impl<Base, Quote> Price<Base, Quote> {
+/// How a [Price] stores its value.
+pub trait PriceValue: Copy + Display {
+    /// Calculate `quote / base`.
+    fn from_ratio(quote: PositiveDecimal, base: PositiveDecimal) -> Result<Self, NumericError>;
+
+    /// Calculate `1 / self`.
+    fn invert(self) -> Result<Self, NumericError>;
+
+    /// Calculate `self * rhs`.
+    fn checked_mul(self, rhs: Self) -> Result<Self, NumericError>;
+
+    /// Calculate `amount * self`, rounded to at most `digits` fractional
+    /// digits in a single step.
+    fn convert(
+        self,
+        amount: UnsignedDecimal,
+        digits: u32,
+        mode: RoundingMode,
+    ) -> Result<UnsignedDecimal, NumericError>;
+
+    /// Convert to a decimal, rounding if needed.
+    fn to_decimal(self, mode: RoundingMode) -> Result<PositiveDecimal, NumericError>;
+
+    /// Split a string into a leading value and the rest, see
+    /// [parse::split_decimal].
+    fn split(s: &str, mode: ParseMode) -> (&str, &str);
+
+    fn parse_with(s: &str, mode: ParseMode) -> Result<Self, NumericError>;
+}
+
+/// Truncates to 6 fractional digits, and renders as a plain number such as
+/// `1.1`.
+impl PriceValue for PositiveDecimal {
+    fn from_ratio(quote: PositiveDecimal, base: PositiveDecimal) -> Result<Self, NumericError> {
+        quote.checked_div(base)
+    }
+
+    /// Truncates, failing with [NumericError::Zero] for very large prices.
+    fn invert(self) -> Result<Self, NumericError> {
+        PositiveDecimal::one().checked_div(self)
+    }
+
+    /// Truncates, failing with [NumericError::Zero] if the product is too
+    /// small.
+    fn checked_mul(self, rhs: Self) -> Result<Self, NumericError> {
+        PositiveDecimal::checked_mul(self, rhs)
+    }
+
+    fn convert(
+        self,
+        amount: UnsignedDecimal,
+        digits: u32,
+        mode: RoundingMode,
+    ) -> Result<UnsignedDecimal, NumericError> {
+        amount.mul_rounded_to(self.get_unsigned(), digits, mode)
+    }
+
+    /// Always exact.
+    fn to_decimal(self, _mode: RoundingMode) -> Result<PositiveDecimal, NumericError> {
+        Ok(self)
+    }
+
+    fn split(s: &str, mode: ParseMode) -> (&str, &str) {
+        parse::split_decimal(s, mode)
+    }
+
+    fn parse_with(s: &str, mode: ParseMode) -> Result<Self, NumericError> {
+        PositiveDecimal::parse_with(s, mode)
+    }
+}
+
+/// Exact, and renders as a fraction such as `11/10`. Never 0.
+impl PriceValue for Rational {
+    fn from_ratio(quote: PositiveDecimal, base: PositiveDecimal) -> Result<Self, NumericError> {
+        Rational::from(quote).checked_div(base.into())
+    }
+
+    fn invert(self) -> Result<Self, NumericError> {
+        self.recip()
+    }
+
+    fn checked_mul(self, rhs: Self) -> Result<Self, NumericError> {
+        Rational::checked_mul(self, rhs)
+    }
+
+    /// Fails with [NumericError::Overflow] if the exact product does not fit
+    /// in a [Rational].
+    fn convert(
+        self,
+        amount: UnsignedDecimal,
+        digits: u32,
+        mode: RoundingMode,
+    ) -> Result<UnsignedDecimal, NumericError> {
+        Rational::from(amount)
+            .checked_mul(self)?
+            .round_to(digits, mode)
+    }
+
+    fn to_decimal(self, mode: RoundingMode) -> Result<PositiveDecimal, NumericError> {
+        Rational::to_decimal(self, mode).and_then(PositiveDecimal::new)
+    }
+
+    fn split(s: &str, mode: ParseMode) -> (&str, &str) {
+        parse::split_rational(s, mode)
+    }
+
+    fn parse_with(s: &str, mode: ParseMode) -> Result<Self, NumericError> {
+        let value = Rational::parse_with(s, mode)?;
+        if value.is_zero() {
+            Err(NumericError::Zero)
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+impl<Base, Quote, Value: PriceValue> Price<Base, Quote, Value> {
+    /// Fails if the ratio is too large, or too small to be represented.
     pub fn from_asset_ratios(
         base: PositiveAsset<Base>,
         quote: PositiveAsset<Quote>,
-    ) -> Price<Base, Quote> {
-        Price {
-            price: quote.get_value() / base.get_value(),
+    ) -> Result<Self, NumericError> {
+        Ok(Price {
+            price: Value::from_ratio(quote.get_value(), base.get_value())?,
             _base: PhantomData,
             _quote: PhantomData,
-        }
+        })
+    }
+
+    /// The price of the quote asset in terms of the base. Exact for
+    /// [Rational] prices.
+    pub fn invert(self) -> Result<Price<Quote, Base, Value>, NumericError> {
+        Ok(Price {
+            price: self.price.invert()?,
+            _base: PhantomData,
+            _quote: PhantomData,
+        })
+    }
+
+    /// The price as a decimal, rounding if needed.
+    pub fn to_decimal(self, mode: RoundingMode) -> Result<PositiveDecimal, NumericError> {
+        self.price.to_decimal(mode)
+    }
+
+    /// The cross rate: given the price of the quote asset in terms of `C`,
+    /// the price of the base asset in terms of `C`. Exact for [Rational]
+    /// prices.
+    pub fn checked_mul<C>(
+        self,
+        rhs: Price<Quote, C, Value>,
+    ) -> Result<Price<Base, C, Value>, NumericError> {
+        Ok(Price {
+            price: self.price.checked_mul(rhs.price)?,
+            _base: PhantomData,
+            _quote: PhantomData,
+        })
+    }
+}
+
+impl<Base> UnsignedAsset<Base> {
+    /// The value of this amount in the quote asset of `price`, rounded to
+    /// the quote asset's [Asset::decimals] with `mode`.
+    ///
+    /// The types make sure the price is for this asset:
+    ///
+    /// ```compile_fail
+    /// use common::{euro, usd, Euro, Price, RoundingMode, Usd};
+    ///
+    /// let price = Price::<Euro, Usd>::from_asset_ratios(euro!(10), usd!(11)).unwrap();
+    /// usd!(5).into_unsigned().convert(price, RoundingMode::Down);
+    /// ```
+    pub fn convert<Quote: Asset, Value: PriceValue>(
+        self,
+        price: Price<Base, Quote, Value>,
+        mode: RoundingMode,
+    ) -> Result<UnsignedAsset<Quote>, NumericError> {
+        price
+            .price
+            .convert(self.get_value(), Quote::decimals(), mode)
+            .map(UnsignedAsset::new_no_hints)
     }
 }
 
-impl<Base: Asset, Quote: Asset> Display for Price<Base, Quote> {
+impl<Base> PositiveAsset<Base> {
+    /// The value of this amount in the quote asset of `price`, rounded to
+    /// the quote asset's [Asset::decimals] with `mode`. Fails with
+    /// [NumericError::Zero] if the result rounds to 0.
+    ///
+    /// ```
+    /// use common::{euro, usd, Euro, Price, RoundingMode, Usd};
+    ///
+    /// let price = Price::<Euro, Usd>::from_asset_ratios(euro!(10), usd!(11)).unwrap();
+    /// assert_eq!(euro!(5).convert(price, RoundingMode::Down).unwrap(), usd!(5.5));
+    /// ```
+    pub fn convert<Quote: Asset, Value: PriceValue>(
+        self,
+        price: Price<Base, Quote, Value>,
+        mode: RoundingMode,
+    ) -> Result<PositiveAsset<Quote>, NumericError> {
+        self.into_unsigned()
+            .convert(price, mode)
+            .and_then(|amount| PositiveDecimal::new(amount.into_decimal()))
+            .map(PositiveAsset::new_no_hints)
+    }
+}
+
+impl<Base: Asset, Quote: Asset, Value: PriceValue> Display for Price<Base, Quote, Value> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{} {}/{}", self.price, Quote::as_str(), Base::as_str(),)
     }
 }
 
-impl<Base: Asset, Quote: Asset> FromStr for Price<Base, Quote> {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        let (price, asset_pair) = split_amount_asset(s)?;
-        let price = price.parse()?;
+impl<Base: Asset, Quote: Asset, Value: PriceValue> Price<Base, Quote, Value> {
+    /// Parse a price such as `1.1 USD/EURO`, or `11/10 USD/EURO` for a
+    /// [Rational] price, using the given grammar for the number.
+    pub fn parse_with(s: &str, mode: ParseMode) -> anyhow::Result<Self> {
+        let (price, asset_pair) = Value::split(s, mode);
+        anyhow::ensure!(!asset_pair.is_empty(), "No asset type found");
+        let price = Value::parse_with(price, mode)?;
         let (quote, base) = asset_pair
             .trim()
             .split_once('/')
@@ -66,7 +261,16 @@ impl<Base: Asset, Quote: Asset> FromStr for Price<Base, Quote> {
     }
 }
 
-impl<Base: Asset, Quote: Asset> serde::Serialize for Price<Base, Quote> {
+/// Parses with [ParseMode::Lenient].
+impl<Base: Asset, Quote: Asset, Value: PriceValue> FromStr for Price<Base, Quote, Value> {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::parse_with(s, ParseMode::Lenient)
+    }
+}
+
+impl<Base: Asset, Quote: Asset, Value: PriceValue> serde::Serialize for Price<Base, Quote, Value> {
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -75,19 +279,23 @@ impl<Base: Asset, Quote: Asset> serde::Serialize for Price<Base, Quote> {
     }
 }
 
-impl<'de, Base: Asset, Quote: Asset> serde::Deserialize<'de> for Price<Base, Quote> {
+impl<'de, Base: Asset, Quote: Asset, Value: PriceValue> serde::Deserialize<'de>
+    for Price<Base, Quote, Value>
+{
     fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        deserializer.deserialize_str(PriceVisitor(PhantomData, PhantomData))
+        deserializer.deserialize_str(PriceVisitor(PhantomData))
     }
 }
 
-struct PriceVisitor<Base, Quote>(PhantomData<Base>, PhantomData<Quote>);
+struct PriceVisitor<Base, Quote, Value>(PhantomData<(Base, Quote, Value)>);
 
-impl<Base: Asset, Quote: Asset> Visitor<'_> for PriceVisitor<Base, Quote> {
-    type Value = Price<Base, Quote>;
+impl<Base: Asset, Quote: Asset, Value: PriceValue> Visitor<'_>
+    for PriceVisitor<Base, Quote, Value>
+{
+    type Value = Price<Base, Quote, Value>;
 
     fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
@@ -102,7 +310,7 @@ impl<Base: Asset, Quote: Asset> Visitor<'_> for PriceVisitor<Base, Quote> {
     where
         E: serde::de::Error,
     {
-        v.parse().map_err(E::custom)
+        Price::parse_with(v, ParseMode::Strict).map_err(crate::de::custom)
     }
 }
 
@@ -114,9 +322,9 @@ mod tests {
 
     #[test]
     fn price_display_is_correct() {
-        let usd = PositiveAsset::from_static(Usd, "11000");
-        let euro = PositiveAsset::from_static(Euro, "10000");
-        let price = Price::from_asset_ratios(euro, usd);
+        let usd = usd!(11000);
+        let euro = euro!(10000);
+        let price = Price::from_asset_ratios(euro, usd).unwrap();
         assert_eq!(price.to_string(), "1.1 USD/EURO");
         assert_eq!(price, price.to_string().parse().unwrap());
 
@@ -125,9 +333,125 @@ mod tests {
         assert_eq!(price, price2);
         serde_json::from_str::<Price<Usd, Euro>>(&json).unwrap_err();
 
-        let btc = PositiveAsset::from_static(Bitcoin, "0.5");
-        let euro = PositiveAsset::from_static(Euro, "55000");
-        let price = Price::from_asset_ratios(btc, euro);
+        let btc = asset!(Bitcoin, 0.5);
+        let euro = euro!(55000);
+        let price = Price::<Bitcoin, Euro>::from_asset_ratios(btc, euro).unwrap();
         assert_eq!(price.to_string(), "110000 EURO/BTC");
+
+        // Finer than the asset's precision, as arithmetic may produce.
+        let tiny = PositiveAsset::new(Euro, pdec!(0.000001));
+        let huge = usd!(10000000);
+        assert_eq!(
+            Price::<Usd, Euro>::from_asset_ratios(huge, tiny),
+            Err(NumericError::Zero)
+        );
+    }
+
+    #[test]
+    fn rational_prices() {
+        let tiny = PositiveAsset::new(Euro, pdec!(0.000001));
+        let huge = usd!(10000000);
+        let price = Price::<Usd, Euro, Rational>::from_asset_ratios(huge, tiny).unwrap();
+        assert_eq!(price.to_string(), "1/10000000000000 EURO/USD");
+        assert_eq!(price, price.to_string().parse().unwrap());
+        assert_eq!(
+            price.to_decimal(RoundingMode::Down),
+            Err(NumericError::Zero)
+        );
+        assert_eq!(
+            price.to_decimal(RoundingMode::Up).unwrap().to_string(),
+            "0.000001"
+        );
+
+        let inverted = price.invert().unwrap();
+        assert_eq!(inverted.to_string(), "10000000000000 USD/EURO");
+        assert_eq!(inverted.invert().unwrap(), price);
+
+        let price = Price::<Euro, Usd, Rational>::from_asset_ratios(euro!(3), usd!(1)).unwrap();
+        let json = serde_json::to_string(&price).unwrap();
+        assert_eq!(json, "\"1/3 USD/EURO\"");
+        assert_eq!(
+            serde_json::from_str::<Price<Euro, Usd, Rational>>(&json).unwrap(),
+            price
+        );
+        serde_json::from_str::<Price<Euro, Usd, Rational>>("\"0 USD/EURO\"").unwrap_err();
+        assert_eq!(
+            price
+                .to_decimal(RoundingMode::HalfEven)
+                .unwrap()
+                .to_string(),
+            "0.333333"
+        );
+
+        let decimal = Price::<Euro, Usd>::from_asset_ratios(euro!(3), usd!(1)).unwrap();
+        assert_eq!(decimal.invert().unwrap().to_string(), "3.000003 EURO/USD");
+    }
+
+    #[test]
+    fn convert() {
+        let price = Price::<Euro, Usd>::from_asset_ratios(euro!(3), usd!(1)).unwrap();
+        assert_eq!(price.to_string(), "0.333333 USD/EURO");
+        let convert = |mode| euro!(10).convert(price, mode).unwrap();
+        assert_eq!(convert(RoundingMode::Down), usd!(3.33));
+        assert_eq!(convert(RoundingMode::Up), usd!(3.34));
+        assert_eq!(convert(RoundingMode::HalfEven), usd!(3.33));
+        assert_eq!(
+            euro!(0.01).convert(price, RoundingMode::Down),
+            Err(NumericError::Zero)
+        );
+        assert_eq!(
+            euro!(0.01)
+                .into_unsigned()
+                .convert(price, RoundingMode::Down),
+            Ok(UnsignedAsset::zero(Usd))
+        );
+
+        // Rational prices are exact until the final rounding.
+        let exact = Price::<Euro, Usd, Rational>::from_asset_ratios(euro!(3), usd!(1)).unwrap();
+        assert_eq!(
+            euro!(1.5).convert(exact, RoundingMode::Down).unwrap(),
+            usd!(0.5)
+        );
+        assert_eq!(
+            euro!(1.5).convert(price, RoundingMode::Down).unwrap(),
+            usd!(0.49)
+        );
+        let back = usd!(0.5).convert(exact.invert().unwrap(), RoundingMode::Down);
+        assert_eq!(back.unwrap(), euro!(1.5));
+
+        // Bitcoin has 6 decimals, so nothing is lost.
+        let btc_price =
+            Price::<Usd, Bitcoin>::from_asset_ratios(usd!(50000), asset!(Bitcoin, 1)).unwrap();
+        assert_eq!(
+            usd!(1).convert(btc_price, RoundingMode::Down).unwrap(),
+            asset!(Bitcoin, 0.00002)
+        );
+    }
+
+    #[test]
+    fn cross_rates() {
+        let btc_euro =
+            Price::<Bitcoin, Euro>::from_asset_ratios(asset!(Bitcoin, 1), euro!(50000)).unwrap();
+        let euro_usd = Price::<Euro, Usd>::from_asset_ratios(euro!(10), usd!(11)).unwrap();
+        let btc_usd: Price<Bitcoin, Usd> = btc_euro.checked_mul(euro_usd).unwrap();
+        assert_eq!(btc_usd.to_string(), "55000 USD/BTC");
+        assert_eq!(
+            euro_usd
+                .checked_mul(euro_usd.invert().unwrap())
+                .unwrap()
+                .to_string(),
+            "0.999999 EURO/EURO"
+        );
+
+        let exact = Price::<Euro, Usd, Rational>::from_asset_ratios(euro!(3), usd!(1)).unwrap();
+        let round_trip: Price<Euro, Euro, Rational> =
+            exact.checked_mul(exact.invert().unwrap()).unwrap();
+        assert_eq!(round_trip.to_string(), "1 EURO/EURO");
+
+        // Decimal prices truncate, so a tiny cross rate can become 0.
+        let euro_usd = Price::<Euro, Usd>::from_asset_ratios(euro!(1000), usd!(1)).unwrap();
+        let usd_btc =
+            Price::<Usd, Bitcoin>::from_asset_ratios(usd!(50000), asset!(Bitcoin, 1)).unwrap();
+        assert_eq!(euro_usd.checked_mul(usd_btc), Err(NumericError::Zero));
     }
 }
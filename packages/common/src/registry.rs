@@ -0,0 +1,495 @@
+//! Assets defined at runtime, e.g. from configuration.
+//!
+//! The [Asset] types are fixed at compile time, so adding one means
+//! rebuilding everything which uses it. An [AssetRegistry] instead holds
+//! assets loaded at runtime, and [AnyAmount] is an amount of any of them.
+//! An [AnyAmount] converts into a typed [UnsignedAsset] or [PositiveAsset]
+//! when its [Denom] matches the type's symbol.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    str::FromStr,
+};
+
+use anyhow::{Context, Result};
+use numeric::{ParseMode, UnsignedDecimal, DEFAULT_SCALE};
+
+use crate::{
+    asset::{check_decimals, split_amount_asset},
+    Asset, AssetDisplay, AssetFormat, NumericError, PositiveAsset, UnsignedAsset,
+};
+
+/// The canonical symbol of an asset, such as `USD`.
+///
+/// A symbol is not empty, contains no whitespace or `/`, and does not start
+/// with anything which could be part of a number.
+#[derive(
+    serde::Serialize, serde::Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Debug,
+)]
+#[serde(try_from = "String", into = "String")]
+pub struct Denom(String);
+
+impl Denom {
+    pub fn new(symbol: impl Into<String>) -> Result<Self> {
+        let symbol = symbol.into();
+        check_symbol(&symbol)?;
+        Ok(Denom(symbol))
+    }
+
+    /// The symbol of a compile-time asset type.
+    pub fn of<T: Asset>() -> Self {
+        Denom(T::as_str().to_owned())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn check_symbol(symbol: &str) -> Result<()> {
+    let first = symbol.chars().next().context("Asset symbol is empty")?;
+    anyhow::ensure!(
+        !first.is_ascii_digit() && !matches!(first, '+' | '-' | '.' | '_'),
+        "Asset symbol {symbol} starts with {first}, which could be part of a number"
+    );
+    anyhow::ensure!(
+        !symbol.contains(|c: char| c.is_whitespace() || c == '/'),
+        "Asset symbol {symbol:?} contains whitespace or /"
+    );
+    Ok(())
+}
+
+impl TryFrom<String> for Denom {
+    type Error = anyhow::Error;
+
+    fn try_from(symbol: String) -> Result<Self> {
+        Denom::new(symbol)
+    }
+}
+
+impl From<Denom> for String {
+    fn from(denom: Denom) -> Self {
+        denom.0
+    }
+}
+
+impl Display for Denom {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Everything the registry knows about an asset.
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Debug)]
+pub struct AssetInfo {
+    /// The canonical symbol, used on the wire.
+    pub symbol: String,
+    /// A name for people to read, e.g. `US Dollar`.
+    pub name: String,
+    /// Maximum number of fractional digits in an amount, at most
+    /// [DEFAULT_SCALE].
+    pub decimals: u32,
+    /// Other symbols accepted when parsing, e.g. `$`.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+impl AssetInfo {
+    /// The metadata of a compile-time asset type, e.g. to register it
+    /// alongside assets from configuration.
+    pub fn of<T: Asset>() -> Self {
+        AssetInfo {
+            symbol: T::as_str().to_owned(),
+            name: T::display_name().to_owned(),
+            decimals: T::decimals(),
+            aliases: T::aliases().iter().map(|alias| alias.to_string()).collect(),
+        }
+    }
+}
+
+/// A set of assets known at runtime, looked up by symbol or alias.
+///
+/// Deserializes from a list of [AssetInfo], checking the same rules as
+/// [AssetRegistry::register].
+#[derive(serde::Serialize, serde::Deserialize, Default, Clone, Debug)]
+#[serde(try_from = "Vec<AssetInfo>", into = "Vec<AssetInfo>")]
+pub struct AssetRegistry {
+    assets: BTreeMap<Denom, AssetInfo>,
+    /// Every symbol and alias, mapped to the canonical symbol.
+    lookup: HashMap<String, Denom>,
+}
+
+impl AssetRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an asset, failing if its symbols are invalid or already in use,
+    /// or if it has more decimals than [DEFAULT_SCALE].
+    pub fn register(&mut self, info: AssetInfo) -> Result<()> {
+        let denom = Denom::new(info.symbol.clone())?;
+        anyhow::ensure!(
+            info.decimals <= DEFAULT_SCALE,
+            "Asset {denom} has {} decimals, at most {DEFAULT_SCALE} are supported",
+            info.decimals
+        );
+        let mut symbols = vec![info.symbol.as_str()];
+        for alias in &info.aliases {
+            check_symbol(alias)?;
+            symbols.push(alias);
+        }
+        for (i, symbol) in symbols.iter().enumerate() {
+            anyhow::ensure!(
+                !self.lookup.contains_key(*symbol) && !symbols[..i].contains(symbol),
+                "Asset symbol {symbol} is already registered"
+            );
+        }
+        for symbol in symbols {
+            self.lookup.insert(symbol.to_owned(), denom.clone());
+        }
+        self.assets.insert(denom, info);
+        Ok(())
+    }
+
+    /// Look up an asset by its symbol or one of its aliases.
+    pub fn get(&self, symbol: &str) -> Option<&AssetInfo> {
+        self.lookup
+            .get(symbol)
+            .and_then(|denom| self.assets.get(denom))
+    }
+
+    /// The canonical symbol for a symbol or alias.
+    pub fn denom(&self, symbol: &str) -> Option<&Denom> {
+        self.lookup.get(symbol)
+    }
+
+    /// All registered assets, ordered by symbol.
+    pub fn iter(&self) -> impl Iterator<Item = &AssetInfo> {
+        self.assets.values()
+    }
+
+    /// Parse an amount followed by a registered symbol or alias, such as
+    /// `5.25USD` or `5.25$`, using the given grammar for the amount.
+    ///
+    /// Aliases are replaced by the canonical symbol, and amounts with more
+    /// fractional digits than the asset allows fail with
+    /// [NumericError::PrecisionLoss].
+    pub fn parse_amount(&self, s: &str, mode: ParseMode) -> Result<AnyAmount> {
+        let (amount, symbol) = split_amount_asset(s, mode)?;
+        let denom = self
+            .denom(symbol)
+            .with_context(|| format!("Unknown asset {symbol}"))?;
+        let amount = AnyAmount {
+            denom: denom.clone(),
+            amount: UnsignedDecimal::parse_with(amount, mode)?,
+        };
+        self.validate(&amount)?;
+        Ok(amount)
+    }
+
+    /// Check that an amount, e.g. one deserialized without the registry, is
+    /// of a registered asset and within its precision.
+    pub fn validate(&self, amount: &AnyAmount) -> Result<()> {
+        let info = self
+            .assets
+            .get(&amount.denom)
+            .with_context(|| format!("Unknown asset {}", amount.denom))?;
+        if amount.amount.fraction_digits() > info.decimals {
+            return Err(NumericError::PrecisionLoss).with_context(|| {
+                format!(
+                    "{} has at most {} decimals, found {}",
+                    info.symbol, info.decimals, amount.amount
+                )
+            });
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<Vec<AssetInfo>> for AssetRegistry {
+    type Error = anyhow::Error;
+
+    fn try_from(assets: Vec<AssetInfo>) -> Result<Self> {
+        let mut registry = AssetRegistry::new();
+        for info in assets {
+            registry.register(info)?;
+        }
+        Ok(registry)
+    }
+}
+
+impl From<AssetRegistry> for Vec<AssetInfo> {
+    fn from(registry: AssetRegistry) -> Self {
+        registry.assets.into_values().collect()
+    }
+}
+
+/// An amount of an asset which is only known at runtime.
+///
+/// Renders and parses like the typed assets, e.g. `5.7USD`. Parsing on its
+/// own accepts any valid symbol, use [AssetRegistry::parse_amount] to
+/// restrict it to registered assets.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct AnyAmount {
+    denom: Denom,
+    amount: UnsignedDecimal,
+}
+
+impl AnyAmount {
+    pub fn new(denom: Denom, amount: UnsignedDecimal) -> Self {
+        AnyAmount { denom, amount }
+    }
+
+    pub fn denom(&self) -> &Denom {
+        &self.denom
+    }
+
+    pub fn amount(&self) -> UnsignedDecimal {
+        self.amount
+    }
+
+    pub fn display_with(&self, format: AssetFormat) -> AssetDisplay<'_> {
+        AssetDisplay {
+            amount: self.amount.into(),
+            symbol: self.denom.as_str(),
+            format,
+        }
+    }
+
+    /// Parse an amount followed by any valid symbol, using the given grammar
+    /// for the amount.
+    pub fn parse_with(s: &str, mode: ParseMode) -> Result<Self> {
+        let (amount, symbol) = split_amount_asset(s, mode)?;
+        Ok(AnyAmount {
+            denom: Denom::new(symbol)?,
+            amount: UnsignedDecimal::parse_with(amount, mode)?,
+        })
+    }
+
+    fn check_denom<T: Asset>(&self) -> Result<()> {
+        anyhow::ensure!(
+            self.denom.as_str() == T::as_str(),
+            "Unexpected asset {} found, expected {}",
+            self.denom,
+            T::as_str()
+        );
+        Ok(())
+    }
+}
+
+/// Renders with [AssetFormat::CANONICAL], like the typed assets.
+impl Display for AnyAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.display_with(AssetFormat::CANONICAL).fmt(f)
+    }
+}
+
+/// Parses with [ParseMode::Lenient].
+impl FromStr for AnyAmount {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_with(s, ParseMode::Lenient)
+    }
+}
+
+impl serde::Serialize for AnyAmount {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AnyAmount {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(AnyAmountVisitor)
+    }
+}
+
+struct AnyAmountVisitor;
+
+impl serde::de::Visitor<'_> for AnyAmountVisitor {
+    type Value = AnyAmount;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("Amount of any asset")
+    }
+
+    fn visit_str<E>(self, v: &str) -> std::result::Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        AnyAmount::parse_with(v, ParseMode::Strict).map_err(crate::de::custom)
+    }
+}
+
+/// Fails unless the symbol is `T::as_str()` and the amount has at most
+/// [Asset::decimals] fractional digits.
+impl<T: Asset> TryFrom<AnyAmount> for UnsignedAsset<T> {
+    type Error = anyhow::Error;
+
+    fn try_from(value: AnyAmount) -> Result<Self> {
+        value.check_denom::<T>()?;
+        check_decimals::<T>(value.amount)?;
+        Ok(UnsignedAsset::new(T::default(), value.amount))
+    }
+}
+
+/// Fails unless the symbol is `T::as_str()`, the amount has at most
+/// [Asset::decimals] fractional digits, and it is not 0.
+impl<T: Asset> TryFrom<AnyAmount> for PositiveAsset<T> {
+    type Error = anyhow::Error;
+
+    fn try_from(value: AnyAmount) -> Result<Self> {
+        value.check_denom::<T>()?;
+        check_decimals::<T>(value.amount)?;
+        let amount = numeric::PositiveDecimal::new(value.amount)?;
+        Ok(PositiveAsset::new(T::default(), amount))
+    }
+}
+
+impl<T: Asset> From<UnsignedAsset<T>> for AnyAmount {
+    fn from(value: UnsignedAsset<T>) -> Self {
+        AnyAmount::new(Denom::of::<T>(), value.into_decimal())
+    }
+}
+
+impl<T: Asset> From<PositiveAsset<T>> for AnyAmount {
+    fn from(value: PositiveAsset<T>) -> Self {
+        value.into_unsigned().into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    fn registry() -> AssetRegistry {
+        serde_json::from_str(
+            r#"[
+                {"symbol": "USD", "name": "US Dollar", "decimals": 2, "aliases": ["$"]},
+                {"symbol": "EURO", "name": "Euro", "decimals": 2},
+                {"symbol": "BTC", "name": "Bitcoin", "decimals": 6, "aliases": ["XBT"]}
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn lookup() {
+        let registry = registry();
+        assert_eq!(registry.get("USD"), Some(&AssetInfo::of::<Usd>()));
+        assert_eq!(registry.get("$").unwrap().name, "US Dollar");
+        assert_eq!(registry.denom("XBT").unwrap().as_str(), "BTC");
+        assert!(registry.get("GBP").is_none());
+        let symbols: Vec<_> = registry.iter().map(|info| info.symbol.as_str()).collect();
+        assert_eq!(symbols, ["BTC", "EURO", "USD"]);
+    }
+
+    #[test]
+    fn register_errors() {
+        let mut registry = registry();
+        let info = |symbol: &str, aliases: &[&str]| AssetInfo {
+            symbol: symbol.to_owned(),
+            name: "Test".to_owned(),
+            decimals: 2,
+            aliases: aliases.iter().map(|s| s.to_string()).collect(),
+        };
+        registry.register(info("$", &[])).unwrap_err();
+        registry.register(info("GBP", &["XBT"])).unwrap_err();
+        registry.register(info("GBP", &["£", "£"])).unwrap_err();
+        registry.register(info("1GBP", &[])).unwrap_err();
+        registry.register(info("GB P", &[])).unwrap_err();
+        registry.register(info("GBP", &["-"])).unwrap_err();
+        registry
+            .register(AssetInfo {
+                decimals: 7,
+                ..info("GBP", &[])
+            })
+            .unwrap_err();
+        // Failed registrations leave nothing behind.
+        assert!(registry.get("£").is_none());
+        registry.register(info("GBP", &["£"])).unwrap();
+        assert_eq!(registry.denom("£").unwrap().as_str(), "GBP");
+
+        serde_json::from_str::<AssetRegistry>(
+            r#"[{"symbol": "USD", "name": "A", "decimals": 2},
+                {"symbol": "USD", "name": "B", "decimals": 2}]"#,
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    fn parse_amount() {
+        let registry = registry();
+        let amount = registry
+            .parse_amount("1_000.25$", ParseMode::Lenient)
+            .unwrap();
+        assert_eq!(amount.to_string(), "1000.25USD");
+        assert_eq!(
+            format!("{:#}", amount.display_with(AssetFormat::FIAT)),
+            "1,000.25 USD"
+        );
+        let err = registry
+            .parse_amount("1.255USD", ParseMode::Lenient)
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<NumericError>(),
+            Some(&NumericError::PrecisionLoss)
+        );
+        registry
+            .parse_amount("1.25GBP", ParseMode::Lenient)
+            .unwrap_err();
+        registry
+            .parse_amount("1.25", ParseMode::Lenient)
+            .unwrap_err();
+
+        let unregistered: AnyAmount = "1.255GBP".parse().unwrap();
+        registry.validate(&unregistered).unwrap_err();
+    }
+
+    #[test]
+    fn typed_conversions() {
+        let amount: AnyAmount = "5.5USD".parse().unwrap();
+        let typed = UnsignedAsset::<Usd>::try_from(amount.clone()).unwrap();
+        assert_eq!(typed, usd!(5.5).into_unsigned());
+        assert_eq!(
+            PositiveAsset::<Usd>::try_from(amount.clone()).unwrap(),
+            usd!(5.5)
+        );
+        UnsignedAsset::<Euro>::try_from(amount.clone()).unwrap_err();
+        assert_eq!(AnyAmount::from(usd!(5.5)), amount);
+
+        let zero = AnyAmount::from(UnsignedAsset::zero(Euro));
+        assert_eq!(zero.to_string(), "0EURO");
+        PositiveAsset::<Euro>::try_from(zero).unwrap_err();
+
+        // Finer than the 2 decimals of USD
+        let fine: AnyAmount = "1.255USD".parse().unwrap();
+        let numeric_error = |e: anyhow::Error| e.downcast::<NumericError>().ok();
+        assert_eq!(
+            UnsignedAsset::<Usd>::try_from(fine.clone()).map_err(numeric_error),
+            Err(Some(NumericError::PrecisionLoss))
+        );
+        assert_eq!(
+            PositiveAsset::<Usd>::try_from(fine).map_err(numeric_error),
+            Err(Some(NumericError::PrecisionLoss))
+        );
+    }
+
+    #[test]
+    fn any_amount_serde() {
+        let amount: AnyAmount = serde_json::from_str(r#""12.5BTC""#).unwrap();
+        assert_eq!(amount.denom(), &Denom::new("BTC").unwrap());
+        assert_eq!(serde_json::to_string(&amount).unwrap(), r#""12.5BTC""#);
+        serde_json::from_str::<AnyAmount>(r#""1.5 BTC""#).unwrap_err();
+        serde_json::from_str::<AnyAmount>(r#""-1BTC""#).unwrap_err();
+        serde_json::from_str::<Denom>(r#""1BTC""#).unwrap_err();
+    }
+}
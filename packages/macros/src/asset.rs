@@ -0,0 +1,245 @@
+//! Implementation of `#[derive(common::Asset)]`, see the `Asset` trait in
+//! `common` for the attributes.
+
+use std::fmt::Write;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    meta::ParseNestedMeta, parse_quote, Data, DeriveInput, Expr, Fields, Ident, LitInt, LitStr,
+    Path,
+};
+
+/// The contents of the `#[asset(...)]` attributes.
+struct AssetAttrs {
+    symbol: LitStr,
+    name: Option<LitStr>,
+    decimals: Option<LitInt>,
+    aliases: Vec<LitStr>,
+    format: Option<Expr>,
+    /// The path to the `common` crate, for crates which rename it.
+    krate: Path,
+}
+
+impl AssetAttrs {
+    fn parse(input: &DeriveInput) -> syn::Result<Self> {
+        let mut symbol = None;
+        let mut name = None;
+        let mut decimals: Option<LitInt> = None;
+        let mut aliases = vec![];
+        let mut format = None;
+        let mut krate: Option<LitStr> = None;
+        for attr in input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("asset"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("symbol") {
+                    set_once(&mut symbol, &meta, "symbol")
+                } else if meta.path.is_ident("name") {
+                    set_once(&mut name, &meta, "name")
+                } else if meta.path.is_ident("decimals") {
+                    set_once(&mut decimals, &meta, "decimals")
+                } else if meta.path.is_ident("alias") {
+                    aliases.push(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("format") {
+                    set_once(&mut format, &meta, "format")
+                } else if meta.path.is_ident("crate") {
+                    set_once(&mut krate, &meta, "crate")
+                } else {
+                    Err(meta
+                        .error("expected one of symbol, name, decimals, alias, format or crate"))
+                }
+            })?;
+        }
+        let symbol = symbol.ok_or_else(|| {
+            syn::Error::new(
+                input.ident.span(),
+                r#"missing #[asset(symbol = "...")] attribute"#,
+            )
+        })?;
+        if let Some(decimals) = &decimals {
+            decimals.base10_parse::<u32>()?;
+        }
+        let krate = match krate {
+            Some(krate) => krate.parse()?,
+            None => parse_quote!(::common),
+        };
+        Ok(AssetAttrs {
+            symbol,
+            name,
+            decimals,
+            aliases,
+            format,
+            krate,
+        })
+    }
+}
+
+fn set_once<T: syn::parse::Parse>(
+    slot: &mut Option<T>,
+    meta: &ParseNestedMeta,
+    key: &str,
+) -> syn::Result<()> {
+    if slot.is_some() {
+        return Err(meta.error(format!("duplicate {key}")));
+    }
+    *slot = Some(meta.value()?.parse()?);
+    Ok(())
+}
+
+/// The same rules as `Denom::new` in `common`, so that every compile-time
+/// asset can also be put in a registry.
+fn check_symbol(lit: &LitStr) -> syn::Result<String> {
+    let symbol = lit.value();
+    let error = |msg| Err(syn::Error::new(lit.span(), msg));
+    let Some(first) = symbol.chars().next() else {
+        return error("asset symbol is empty".to_owned());
+    };
+    if first.is_ascii_digit() || matches!(first, '+' | '-' | '.' | '_') {
+        return error(format!(
+            "asset symbol {symbol} starts with {first}, which could be part of a number"
+        ));
+    }
+    if symbol.contains(|c: char| c.is_whitespace() || c == '/') {
+        return error(format!("asset symbol {symbol:?} contains whitespace or /"));
+    }
+    Ok(symbol)
+}
+
+pub(crate) fn derive(input: DeriveInput) -> syn::Result<TokenStream> {
+    let ident = &input.ident;
+    let is_unit = matches!(&input.data, Data::Struct(data) if matches!(data.fields, Fields::Unit));
+    if !is_unit || !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            ident.span(),
+            "Asset can only be derived for a unit struct without generics",
+        ));
+    }
+    let AssetAttrs {
+        symbol,
+        name,
+        decimals,
+        aliases,
+        format,
+        krate,
+    } = AssetAttrs::parse(&input)?;
+
+    // A #[macro_export] macro is defined in the crate root, whichever module
+    // it comes from, so two assets claiming the same symbol or alias define
+    // the same name twice and the compiler rejects the crate. The hex
+    // encoding turns any symbol into a valid identifier. Exporting a macro
+    // from a function body is deliberate here, so allow the lint against it.
+    let mut markers = vec![];
+    for lit in std::iter::once(&symbol).chain(&aliases) {
+        let mut marker = "__asset_symbol_".to_owned();
+        for b in check_symbol(lit)?.bytes() {
+            write!(marker, "{b:02x}").unwrap();
+        }
+        let marker = Ident::new(&marker, lit.span());
+        markers.push(quote! {
+            #[doc(hidden)]
+            #[allow(non_local_definitions)]
+            #[macro_export]
+            macro_rules! #marker {
+                () => {};
+            }
+        });
+    }
+
+    let display_name = name.map(|name| {
+        quote! {
+            fn display_name() -> &'static str {
+                #name
+            }
+        }
+    });
+    let decimals_check = decimals.as_ref().map(|decimals| {
+        quote! {
+            const _: () = assert!(
+                #decimals <= #krate::__private::numeric::DEFAULT_SCALE,
+                "asset decimals must be at most DEFAULT_SCALE"
+            );
+        }
+    });
+    let decimals = decimals.map(|decimals| {
+        quote! {
+            const DECIMALS: u32 = #decimals;
+        }
+    });
+    let aliases = (!aliases.is_empty()).then(|| {
+        quote! {
+            fn aliases() -> &'static [&'static str] {
+                &[#(#aliases),*]
+            }
+        }
+    });
+    let format = format.map(|format| {
+        quote! {
+            fn format() -> #krate::AssetFormat {
+                #format
+            }
+        }
+    });
+
+    Ok(quote! {
+        #(#markers)*
+        #decimals_check
+
+        impl #krate::Asset for #ident {
+            #decimals
+
+            fn as_str() -> &'static str {
+                #symbol
+            }
+
+            #display_name
+            #aliases
+            #format
+        }
+
+        // The supertraits of Asset, plus Clone and Copy. A unit struct has a
+        // single value, so all of these are trivial.
+        impl ::core::clone::Clone for #ident {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl ::core::marker::Copy for #ident {}
+
+        impl ::core::cmp::PartialEq for #ident {
+            fn eq(&self, _: &Self) -> bool {
+                true
+            }
+        }
+
+        impl ::core::cmp::Eq for #ident {}
+
+        impl ::core::cmp::PartialOrd for #ident {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl ::core::cmp::Ord for #ident {
+            fn cmp(&self, _: &Self) -> ::core::cmp::Ordering {
+                ::core::cmp::Ordering::Equal
+            }
+        }
+
+        impl ::core::fmt::Debug for #ident {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                f.write_str(stringify!(#ident))
+            }
+        }
+
+        impl ::core::default::Default for #ident {
+            fn default() -> Self {
+                #ident
+            }
+        }
+    })
+}
@@ -0,0 +1,114 @@
+//! Procedural macros for the `numeric` and `common` crates.
+//!
+//! These are implementation details: use the `dec!` and `pdec!` macros
+//! exported by `numeric`, which call into this crate with the path to
+//! `numeric` itself, and `#[derive(Asset)]` exported by `common`.
+
+mod asset;
+
+use proc_macro::TokenStream;
+use proc_macro2::Literal;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, DeriveInput, Lit, Path, Token,
+};
+
+/// Input to the decimal macros: the path to the `numeric` crate, then a
+/// numeric literal.
+struct DecimalInput {
+    krate: Path,
+    whole: u128,
+    fraction: u128,
+    fraction_digits: u32,
+}
+
+impl Parse for DecimalInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![;]>()?;
+        let lit: Lit = input.parse()?;
+        let (digits, suffix) = match &lit {
+            Lit::Int(lit) => (lit.base10_digits(), lit.suffix()),
+            Lit::Float(lit) => (lit.base10_digits(), lit.suffix()),
+            _ => return Err(syn::Error::new(lit.span(), "expected a decimal literal")),
+        };
+        let error = |msg| Err(syn::Error::new(lit.span(), msg));
+        // base10_digits converts hex, octal and binary integers, so check the
+        // original token as well.
+        let token = lit.to_token_stream().to_string();
+        if token.starts_with("0x") || token.starts_with("0o") || token.starts_with("0b") {
+            return error("expected a base 10 literal");
+        }
+        if !suffix.is_empty() {
+            return error("expected a plain decimal literal without a suffix");
+        }
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+        {
+            return error("expected a plain decimal literal without an exponent");
+        }
+        let Ok(whole) = whole.parse() else {
+            return error("value is too large");
+        };
+        let fraction_digits = fraction.len() as u32;
+        let fraction = if fraction.is_empty() {
+            0
+        } else if let (true, Ok(fraction)) = (fraction_digits <= 38, fraction.parse()) {
+            fraction
+        } else {
+            return error("too many fractional digits");
+        };
+        if !input.is_empty() {
+            return Err(input.error("unexpected tokens after the literal"));
+        }
+        Ok(DecimalInput {
+            krate,
+            whole,
+            fraction,
+            fraction_digits,
+        })
+    }
+}
+
+fn expand(input: DecimalInput, constructor: &str) -> TokenStream {
+    let DecimalInput {
+        krate,
+        whole,
+        fraction,
+        fraction_digits,
+    } = input;
+    let constructor = syn::Ident::new(constructor, proc_macro2::Span::call_site());
+    let whole = Literal::u128_suffixed(whole);
+    let fraction = Literal::u128_suffixed(fraction);
+    let fraction_digits = Literal::u32_suffixed(fraction_digits);
+    // Evaluate in a const block so that range errors are reported at compile
+    // time, even when the macro is used in a non-const context.
+    quote! {
+        const { #krate::__private::#constructor(#whole, #fraction, #fraction_digits) }
+    }
+    .into()
+}
+
+/// Implementation of `numeric::dec!`.
+#[proc_macro]
+pub fn dec(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DecimalInput), "unsigned")
+}
+
+/// Implementation of `numeric::pdec!`.
+#[proc_macro]
+pub fn pdec(input: TokenStream) -> TokenStream {
+    expand(parse_macro_input!(input as DecimalInput), "positive")
+}
+
+/// Implementation of `#[derive(common::Asset)]`.
+#[proc_macro_derive(Asset, attributes(asset))]
+pub fn derive_asset(input: TokenStream) -> TokenStream {
+    asset::derive(parse_macro_input!(input as DeriveInput))
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,73 @@
+//! Benchmarks for the core decimal operations, see BENCHMARKS.md for the
+//! baseline results.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use numeric::{dec, ParseMode, RoundingMode, SignedDecimal, UnsignedDecimal};
+
+/// A small value, a typical amount and the largest value.
+const INPUTS: [&str; 3] = [
+    "1.5",
+    "103000.123456",
+    "340282366920938463463374607431768.211455",
+];
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for s in INPUTS {
+        group.bench_function(s, |b| {
+            b.iter(|| UnsignedDecimal::parse_with(black_box(s), ParseMode::Strict))
+        });
+    }
+    group.bench_function("lenient 1_000_000.5e-1", |b| {
+        b.iter(|| UnsignedDecimal::parse_with(black_box("1_000_000.5e-1"), ParseMode::Lenient))
+    });
+    group.bench_function("signed -103000.123456", |b| {
+        b.iter(|| black_box("-103000.123456").parse::<SignedDecimal>())
+    });
+    group.finish();
+}
+
+fn display(c: &mut Criterion) {
+    let mut group = c.benchmark_group("display");
+    for s in INPUTS {
+        let x: UnsignedDecimal = s.parse().unwrap();
+        group.bench_function(s, |b| b.iter(|| black_box(x).to_string()));
+    }
+    group.finish();
+}
+
+fn mul(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mul");
+    let (x, y) = (dec!(103000.123456), dec!(0.970873));
+    group.bench_function("checked_mul", |b| {
+        b.iter(|| black_box(x).checked_mul(black_box(y)))
+    });
+    group.bench_function("mul_rounded HalfEven", |b| {
+        b.iter(|| black_box(x).mul_rounded(black_box(y), RoundingMode::HalfEven))
+    });
+    let big = UnsignedDecimal::MAX;
+    group.bench_function("mul_rounded wide", |b| {
+        b.iter(|| black_box(big).mul_rounded(black_box(y), RoundingMode::Down))
+    });
+    group.finish();
+}
+
+fn div(c: &mut Criterion) {
+    let mut group = c.benchmark_group("div");
+    let (x, y) = (dec!(100000), dec!(103000.123456));
+    group.bench_function("checked_div", |b| {
+        b.iter(|| black_box(x).checked_div(black_box(y)))
+    });
+    group.bench_function("div_rounded Up", |b| {
+        b.iter(|| black_box(x).div_rounded(black_box(y), RoundingMode::Up))
+    });
+    group.bench_function("mul_div Up", |b| {
+        b.iter(|| black_box(x).mul_div(black_box(x), black_box(y), RoundingMode::Up))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse, display, mul, div);
+criterion_main!(benches);
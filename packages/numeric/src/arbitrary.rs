@@ -0,0 +1,115 @@
+//! [arbitrary] support, enabled by the `arbitrary` feature, for fuzzing.
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{
+    BasisPoints, Percent, Ratio, Rational, ScaledPositiveDecimal, ScaledSignedDecimal,
+    ScaledUnsignedDecimal, UnsignedDecimal,
+};
+
+impl<'a, const SCALE: u32> Arbitrary<'a> for ScaledUnsignedDecimal<SCALE> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u128::arbitrary(u).map(Self::from_raw_value)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u128::size_hint(depth)
+    }
+}
+
+impl<'a, const SCALE: u32> Arbitrary<'a> for ScaledPositiveDecimal<SCALE> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let value = ScaledUnsignedDecimal::from_raw_value(u.int_in_range(1..=u128::MAX)?);
+        Ok(Self::new(value).expect("arbitrary: raw value is never 0"))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u128::size_hint(depth)
+    }
+}
+
+impl<'a, const SCALE: u32> Arbitrary<'a> for ScaledSignedDecimal<SCALE> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let value = ScaledUnsignedDecimal::arbitrary(u)?;
+        // from_raw_value never produces a negative 0.
+        Ok(Self::from_raw_value(value, bool::arbitrary(u)?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <(u128, bool)>::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Ratio {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let raw = u.int_in_range(0..=UnsignedDecimal::MULTIPLIER)?;
+        Ok(Self::new(UnsignedDecimal::from_raw_value(raw))
+            .expect("arbitrary: raw value is never above 1"))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u128::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Percent {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ratio::arbitrary(u).map(Self::from)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Ratio::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for BasisPoints {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ratio::arbitrary(u).map(Self::from)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Ratio::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Rational {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let numer = u128::arbitrary(u)?;
+        let denom = u.int_in_range(1..=u128::MAX)?;
+        Ok(Self::new(numer, denom).expect("arbitrary: denominator is never 0"))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <(u128, u128)>::size_hint(depth)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PositiveDecimal, SignedDecimal};
+
+    use super::*;
+
+    #[test]
+    fn test_edge_cases() {
+        let zeros = [0; 64];
+        let mut u = Unstructured::new(&zeros);
+        assert_eq!(
+            UnsignedDecimal::arbitrary(&mut u),
+            Ok(UnsignedDecimal::zero())
+        );
+        assert_eq!(PositiveDecimal::arbitrary(&mut u), Ok(PositiveDecimal::MIN));
+        assert_eq!(SignedDecimal::arbitrary(&mut u), Ok(SignedDecimal::zero()));
+
+        // A negative 0 is never produced, even when the sign byte is set.
+        let mut bytes = [0; 17];
+        bytes[16] = 1;
+        let mut u = Unstructured::new(&bytes);
+        assert_eq!(SignedDecimal::arbitrary(&mut u), Ok(SignedDecimal::zero()));
+
+        let ones = [0xff; 64];
+        let mut u = Unstructured::new(&ones);
+        assert!(Ratio::arbitrary(&mut u).unwrap() <= Ratio::ONE);
+        assert_eq!(Rational::arbitrary(&mut u).map(|r| r.denom() > 0), Ok(true));
+    }
+}
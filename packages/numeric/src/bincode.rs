@@ -0,0 +1,69 @@
+//! [bincode] 2 support, enabled by the `bincode` feature, using the
+//! [binary encoding](crate#binary-encoding).
+
+use ::bincode::{
+    de::{BorrowDecoder, Decoder},
+    enc::Encoder,
+    error::{DecodeError, EncodeError},
+    BorrowDecode, Decode, Encode,
+};
+
+use crate::{Error, ScaledPositiveDecimal, ScaledSignedDecimal, ScaledUnsignedDecimal};
+
+fn decode_error(e: Error) -> DecodeError {
+    DecodeError::Other(match e {
+        Error::Zero => "a positive decimal cannot be 0",
+        Error::NegativeZero => "a signed decimal cannot be negative 0",
+        _ => "invalid decimal encoding",
+    })
+}
+
+macro_rules! impl_bincode {
+    ($ty:ident, $len:expr, $from_bytes:expr) => {
+        impl<const SCALE: u32> Encode for $ty<SCALE> {
+            fn encode<E: Encoder>(&self, encoder: &mut E) -> Result<(), EncodeError> {
+                self.to_bytes().encode(encoder)
+            }
+        }
+
+        impl<const SCALE: u32> Decode for $ty<SCALE> {
+            fn decode<D: Decoder>(decoder: &mut D) -> Result<Self, DecodeError> {
+                $from_bytes(<[u8; $len]>::decode(decoder)?)
+            }
+        }
+
+        impl<'de, const SCALE: u32> BorrowDecode<'de> for $ty<SCALE> {
+            fn borrow_decode<D: BorrowDecoder<'de>>(decoder: &mut D) -> Result<Self, DecodeError> {
+                Self::decode(decoder)
+            }
+        }
+    };
+}
+
+impl_bincode!(ScaledUnsignedDecimal, 16, |bytes| Ok(Self::from_bytes(
+    bytes
+)));
+impl_bincode!(ScaledPositiveDecimal, 16, |bytes| Self::from_bytes(bytes)
+    .map_err(decode_error));
+impl_bincode!(ScaledSignedDecimal, 17, |bytes| Self::from_bytes(bytes)
+    .map_err(decode_error));
+
+#[cfg(test)]
+mod tests {
+    use crate::{dec, PositiveDecimal, SignedDecimal, UnsignedDecimal};
+
+    #[test]
+    fn test_bincode() {
+        let config = bincode::config::standard();
+        let x = dec!(1.5);
+        let y: SignedDecimal = "-2.25".parse().unwrap();
+        let bytes = bincode::encode_to_vec((x, y), config).unwrap();
+        assert_eq!(bytes.len(), 33);
+        assert_eq!(bytes[..16], x.to_bytes());
+        let (decoded, _): ((UnsignedDecimal, SignedDecimal), _) =
+            bincode::decode_from_slice(&bytes, config).unwrap();
+        assert_eq!(decoded, (x, y));
+
+        bincode::decode_from_slice::<PositiveDecimal, _>(&[0; 16], config).unwrap_err();
+    }
+}
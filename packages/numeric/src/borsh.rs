@@ -0,0 +1,76 @@
+//! [borsh] support, enabled by the `borsh` feature, using the
+//! [binary encoding](crate#binary-encoding).
+
+use alloc::string::ToString;
+
+use ::borsh::{
+    io::{Error as IoError, ErrorKind, Read, Result as IoResult, Write},
+    BorshDeserialize, BorshSerialize,
+};
+
+use crate::{Error, ScaledPositiveDecimal, ScaledSignedDecimal, ScaledUnsignedDecimal};
+
+fn invalid_data(e: Error) -> IoError {
+    IoError::new(ErrorKind::InvalidData, e.to_string())
+}
+
+impl<const SCALE: u32> BorshSerialize for ScaledUnsignedDecimal<SCALE> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+impl<const SCALE: u32> BorshDeserialize for ScaledUnsignedDecimal<SCALE> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> IoResult<Self> {
+        <[u8; 16]>::deserialize_reader(reader).map(Self::from_bytes)
+    }
+}
+
+impl<const SCALE: u32> BorshSerialize for ScaledPositiveDecimal<SCALE> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+impl<const SCALE: u32> BorshDeserialize for ScaledPositiveDecimal<SCALE> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> IoResult<Self> {
+        Self::from_bytes(<[u8; 16]>::deserialize_reader(reader)?).map_err(invalid_data)
+    }
+}
+
+impl<const SCALE: u32> BorshSerialize for ScaledSignedDecimal<SCALE> {
+    fn serialize<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        writer.write_all(&self.to_bytes())
+    }
+}
+
+impl<const SCALE: u32> BorshDeserialize for ScaledSignedDecimal<SCALE> {
+    fn deserialize_reader<R: Read>(reader: &mut R) -> IoResult<Self> {
+        Self::from_bytes(<[u8; 17]>::deserialize_reader(reader)?).map_err(invalid_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{dec, PositiveDecimal, SignedDecimal, UnsignedDecimal};
+
+    #[test]
+    fn test_borsh() {
+        let x = dec!(1.5);
+        let bytes = borsh::to_vec(&x).unwrap();
+        assert_eq!(bytes, x.to_bytes());
+        assert_eq!(borsh::from_slice::<UnsignedDecimal>(&bytes).unwrap(), x);
+        borsh::from_slice::<PositiveDecimal>(&[0; 16]).unwrap_err();
+
+        let y: SignedDecimal = "-2.25".parse().unwrap();
+        let bytes = borsh::to_vec(&(x, y)).unwrap();
+        assert_eq!(bytes.len(), 33);
+        assert_eq!(
+            borsh::from_slice::<(UnsignedDecimal, SignedDecimal)>(&bytes).unwrap(),
+            (x, y)
+        );
+        let mut bad_sign = bytes;
+        bad_sign[32] = 2;
+        borsh::from_slice::<(UnsignedDecimal, SignedDecimal)>(&bad_sign).unwrap_err();
+    }
+}
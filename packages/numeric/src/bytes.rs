@@ -0,0 +1,116 @@
+//! Fixed-size binary encoding, see the [crate docs](crate#binary-encoding).
+
+use crate::{Error, ScaledPositiveDecimal, ScaledSignedDecimal, ScaledUnsignedDecimal};
+
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    /// Size of the binary encoding, see [ScaledUnsignedDecimal::to_bytes].
+    pub const ENCODED_LEN: usize = 16;
+
+    /// Encode as the little-endian raw number of units.
+    pub const fn to_bytes(&self) -> [u8; 16] {
+        self.get_raw_value().to_le_bytes()
+    }
+
+    /// Decode the output of [ScaledUnsignedDecimal::to_bytes]. Every input
+    /// is valid.
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self::from_raw_value(u128::from_le_bytes(bytes))
+    }
+}
+
+impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+    /// Size of the binary encoding, see [ScaledPositiveDecimal::to_bytes].
+    pub const ENCODED_LEN: usize = 16;
+
+    /// Encode as the little-endian raw number of units.
+    pub fn to_bytes(&self) -> [u8; 16] {
+        self.get_unsigned().to_bytes()
+    }
+
+    /// Decode the output of [ScaledPositiveDecimal::to_bytes], failing with
+    /// [Error::Zero] for 0.
+    pub fn from_bytes(bytes: [u8; 16]) -> Result<Self, Error> {
+        Self::new(ScaledUnsignedDecimal::from_bytes(bytes))
+    }
+}
+
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    /// Size of the binary encoding, see [ScaledSignedDecimal::to_bytes].
+    pub const ENCODED_LEN: usize = 17;
+
+    /// Encode as the little-endian raw magnitude, followed by a sign byte.
+    pub fn to_bytes(&self) -> [u8; 17] {
+        let mut bytes = [0; 17];
+        bytes[..16].copy_from_slice(&self.get_raw_value().to_bytes());
+        bytes[16] = u8::from(self.is_negative());
+        bytes
+    }
+
+    /// Decode the output of [ScaledSignedDecimal::to_bytes], failing with
+    /// [Error::NegativeZero] for a negative 0 and [Error::InvalidEncoding]
+    /// for a sign byte other than 0 or 1.
+    pub fn from_bytes(bytes: [u8; 17]) -> Result<Self, Error> {
+        let mut magnitude = [0; 16];
+        magnitude.copy_from_slice(&bytes[..16]);
+        let magnitude = ScaledUnsignedDecimal::from_bytes(magnitude);
+        match bytes[16] {
+            0 => Ok(Self::from_raw_value(magnitude, false)),
+            1 if magnitude.get_raw_value() == 0 => Err(Error::NegativeZero),
+            1 => Ok(Self::from_raw_value(magnitude, true)),
+            _ => Err(Error::InvalidEncoding),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{dec, pdec, PositiveDecimal, SignedDecimal, UnsignedDecimal};
+
+    use super::*;
+
+    #[test]
+    fn test_unsigned() {
+        let x = dec!(1.5);
+        assert_eq!(x.to_bytes()[..4], [0x60, 0xe3, 0x16, 0x00]);
+        assert_eq!(UnsignedDecimal::from_bytes(x.to_bytes()), x);
+        assert_eq!(
+            UnsignedDecimal::from_bytes([0xff; 16]),
+            UnsignedDecimal::MAX
+        );
+    }
+
+    #[test]
+    fn test_positive() {
+        let x = pdec!(0.000001);
+        assert_eq!(x.to_bytes(), dec!(0.000001).to_bytes());
+        assert_eq!(PositiveDecimal::from_bytes(x.to_bytes()), Ok(x));
+        assert_eq!(PositiveDecimal::from_bytes([0; 16]), Err(Error::Zero));
+    }
+
+    #[test]
+    fn test_signed() {
+        let x: SignedDecimal = "-1.5".parse().unwrap();
+        let bytes = x.to_bytes();
+        assert_eq!(bytes[..16], dec!(1.5).to_bytes());
+        assert_eq!(bytes[16], 1);
+        assert_eq!(SignedDecimal::from_bytes(bytes), Ok(x));
+        assert_eq!(SignedDecimal::zero().to_bytes(), [0; 17]);
+        assert_eq!(
+            SignedDecimal::from_bytes(SignedDecimal::MIN.to_bytes()),
+            Ok(SignedDecimal::MIN)
+        );
+
+        let mut negative_zero = [0; 17];
+        negative_zero[16] = 1;
+        assert_eq!(
+            SignedDecimal::from_bytes(negative_zero),
+            Err(Error::NegativeZero)
+        );
+        let mut bad_sign = bytes;
+        bad_sign[16] = 2;
+        assert_eq!(
+            SignedDecimal::from_bytes(bad_sign),
+            Err(Error::InvalidEncoding)
+        );
+    }
+}
@@ -0,0 +1,439 @@
+//! Conversions between the decimal types, primitive integers and floats.
+
+use crate::{
+    wide::U256, Error, RoundingMode, ScaledPositiveDecimal, ScaledSignedDecimal,
+    ScaledUnsignedDecimal,
+};
+
+/// Raw units, i.e. the value multiplied by `10^SCALE`.
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    /// Build a value from its raw number of units, e.g. `1500000` for `1.5`
+    /// with the default scale of 6.
+    pub const fn from_raw_units(units: u128) -> Self {
+        Self::from_raw_value(units)
+    }
+
+    /// The raw number of units, i.e. the value multiplied by `10^SCALE`.
+    pub const fn to_raw_units(self) -> u128 {
+        self.get_raw_value()
+    }
+}
+
+impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+    /// Build a value from its raw number of units, failing on 0.
+    pub fn from_raw_units(units: u128) -> Result<Self, Error> {
+        Self::new(ScaledUnsignedDecimal::from_raw_units(units))
+    }
+
+    /// The raw number of units, i.e. the value multiplied by `10^SCALE`.
+    pub fn to_raw_units(self) -> u128 {
+        self.get_unsigned().to_raw_units()
+    }
+}
+
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    /// Build a value from the raw number of units of its magnitude and its
+    /// sign. A magnitude of 0 is never negative.
+    pub fn from_raw_units(magnitude: u128, negative: bool) -> Self {
+        Self::from_raw_value(ScaledUnsignedDecimal::from_raw_units(magnitude), negative)
+    }
+
+    /// The raw number of units of the magnitude, and whether the value is
+    /// negative.
+    pub fn to_raw_units(self) -> (u128, bool) {
+        (self.get_raw_value().to_raw_units(), self.is_negative())
+    }
+}
+
+/// Float conversions.
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    /// Convert from an `f64`, rounding its exact value according to `mode`.
+    ///
+    /// Fails with [Error::NotFinite] for NaN and infinities, and with
+    /// [Error::Underflow] for negative values which do not round to 0.
+    pub fn from_f64(value: f64, mode: RoundingMode) -> Result<Self, Error> {
+        match f64_to_raw(value, Self::MULTIPLIER, mode)? {
+            (magnitude, true) if magnitude != 0 => Err(Error::Underflow),
+            (magnitude, _) => Ok(Self::from_raw_value(magnitude)),
+        }
+    }
+
+    /// Convert to the nearest `f64`, or within one unit in the last place
+    /// of it. Values with more than about 15 significant digits lose
+    /// precision.
+    pub fn to_f64_lossy(self) -> f64 {
+        self.get_raw_value() as f64 / Self::MULTIPLIER as f64
+    }
+}
+
+impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+    /// Convert from an `f64`, rounding its exact value according to `mode`.
+    ///
+    /// Fails with [Error::Zero] if the result rounds to 0.
+    pub fn from_f64(value: f64, mode: RoundingMode) -> Result<Self, Error> {
+        ScaledUnsignedDecimal::from_f64(value, mode).and_then(Self::new)
+    }
+
+    /// See [ScaledUnsignedDecimal::to_f64_lossy].
+    pub fn to_f64_lossy(self) -> f64 {
+        self.get_unsigned().to_f64_lossy()
+    }
+}
+
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    /// Convert from an `f64`, rounding its exact value according to `mode`.
+    ///
+    /// Fails with [Error::NotFinite] for NaN and infinities.
+    pub fn from_f64(value: f64, mode: RoundingMode) -> Result<Self, Error> {
+        let (magnitude, negative) =
+            f64_to_raw(value, ScaledUnsignedDecimal::<SCALE>::MULTIPLIER, mode)?;
+        Ok(Self::from_raw_units(magnitude, negative))
+    }
+
+    /// See [ScaledUnsignedDecimal::to_f64_lossy].
+    pub fn to_f64_lossy(self) -> f64 {
+        let magnitude = self.get_raw_value().to_f64_lossy();
+        if self.is_negative() {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+}
+
+/// Convert the exact value of `value * multiplier` to an integer, returning
+/// the rounded magnitude and whether `value` is negative.
+fn f64_to_raw(value: f64, multiplier: u128, mode: RoundingMode) -> Result<(u128, bool), Error> {
+    if !value.is_finite() {
+        return Err(Error::NotFinite);
+    }
+    let bits = value.to_bits();
+    let negative = bits >> 63 == 1;
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    // value = mantissa * 2^exponent, exactly.
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | 1 << 52, biased_exponent - 1075)
+    };
+    // Less than 2^180, so this always fits.
+    let scaled = U256::mul_u128(mantissa.into(), multiplier);
+    let magnitude = if exponent >= 0 {
+        scaled
+            .checked_shl(exponent.unsigned_abs())
+            .and_then(U256::to_u128)
+    } else {
+        let mut shift = exponent.unsigned_abs();
+        let mut scaled = scaled;
+        if shift > 127 {
+            // The divisor must fit in a u128. Drop the extra low bits first,
+            // keeping a sticky bit so that rounding still sees a non-zero
+            // remainder below the halfway point.
+            let extra = shift - 127;
+            let kept = scaled.shr(extra);
+            let exact = kept.checked_shl(extra) == Some(scaled);
+            scaled = if exact || kept.low_u128() % 2 == 1 {
+                kept
+            } else {
+                kept.checked_add_u128(1)
+                    .expect("cannot overflow after a shift")
+            };
+            shift = 127;
+        }
+        scaled.div_rounded(1 << shift, negative, mode)
+    };
+    match magnitude {
+        Some(magnitude) => Ok((magnitude, negative)),
+        None if negative => Err(Error::Underflow),
+        None => Err(Error::Overflow),
+    }
+}
+
+impl<const SCALE: u32> TryFrom<f64> for ScaledUnsignedDecimal<SCALE> {
+    type Error = Error;
+
+    /// Rounds to the nearest value, see [ScaledUnsignedDecimal::from_f64].
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::from_f64(value, RoundingMode::HalfEven)
+    }
+}
+
+impl<const SCALE: u32> TryFrom<f64> for ScaledPositiveDecimal<SCALE> {
+    type Error = Error;
+
+    /// Rounds to the nearest value, see [ScaledPositiveDecimal::from_f64].
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::from_f64(value, RoundingMode::HalfEven)
+    }
+}
+
+impl<const SCALE: u32> TryFrom<f64> for ScaledSignedDecimal<SCALE> {
+    type Error = Error;
+
+    /// Rounds to the nearest value, see [ScaledSignedDecimal::from_f64].
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::from_f64(value, RoundingMode::HalfEven)
+    }
+}
+
+/// Integer conversions.
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    fn from_integer(value: u128) -> Result<Self, Error> {
+        value
+            .checked_mul(Self::MULTIPLIER)
+            .map(Self::from_raw_value)
+            .ok_or(Error::Overflow)
+    }
+}
+
+/// Infallible conversions from integer types, which fail to compile if the
+/// scale is too large for every value of the integer type to fit.
+macro_rules! impl_from_unsigned_int {
+    ($($int:ty),*) => {$(
+        impl<const SCALE: u32> From<$int> for ScaledUnsignedDecimal<SCALE> {
+            fn from(value: $int) -> Self {
+                const {
+                    assert!(
+                        <$int>::MAX as u128 <= u128::MAX / Self::MULTIPLIER,
+                        "Integer type is too large for this scale"
+                    )
+                };
+                Self::from_raw_value(u128::from(value) * Self::MULTIPLIER)
+            }
+        }
+
+        impl<const SCALE: u32> From<$int> for ScaledSignedDecimal<SCALE> {
+            fn from(value: $int) -> Self {
+                ScaledUnsignedDecimal::from(value).into()
+            }
+        }
+
+        impl<const SCALE: u32> TryFrom<$int> for ScaledPositiveDecimal<SCALE> {
+            type Error = Error;
+
+            fn try_from(value: $int) -> Result<Self, Self::Error> {
+                Self::new(value.into())
+            }
+        }
+    )*};
+}
+
+impl_from_unsigned_int!(u8, u16, u32, u64);
+
+macro_rules! impl_from_signed_int {
+    ($($int:ty),*) => {$(
+        impl<const SCALE: u32> From<$int> for ScaledSignedDecimal<SCALE> {
+            fn from(value: $int) -> Self {
+                let magnitude = ScaledUnsignedDecimal::from(value.unsigned_abs());
+                Self::from_raw_value(magnitude, value < 0)
+            }
+        }
+    )*};
+}
+
+impl_from_signed_int!(i8, i16, i32, i64);
+
+impl<const SCALE: u32> TryFrom<u128> for ScaledUnsignedDecimal<SCALE> {
+    type Error = Error;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        Self::from_integer(value)
+    }
+}
+
+impl<const SCALE: u32> TryFrom<u128> for ScaledPositiveDecimal<SCALE> {
+    type Error = Error;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        ScaledUnsignedDecimal::try_from(value).and_then(Self::new)
+    }
+}
+
+impl<const SCALE: u32> TryFrom<u128> for ScaledSignedDecimal<SCALE> {
+    type Error = Error;
+
+    fn try_from(value: u128) -> Result<Self, Self::Error> {
+        ScaledUnsignedDecimal::try_from(value).map(Self::from)
+    }
+}
+
+impl<const SCALE: u32> TryFrom<i128> for ScaledSignedDecimal<SCALE> {
+    type Error = Error;
+
+    fn try_from(value: i128) -> Result<Self, Self::Error> {
+        match ScaledUnsignedDecimal::from_integer(value.unsigned_abs()) {
+            Ok(magnitude) => Ok(Self::from_raw_value(magnitude, value < 0)),
+            Err(_) if value < 0 => Err(Error::Underflow),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Conversions between the decimal types.
+impl<const SCALE: u32> From<ScaledPositiveDecimal<SCALE>> for ScaledUnsignedDecimal<SCALE> {
+    fn from(value: ScaledPositiveDecimal<SCALE>) -> Self {
+        value.get_unsigned()
+    }
+}
+
+impl<const SCALE: u32> From<ScaledPositiveDecimal<SCALE>> for ScaledSignedDecimal<SCALE> {
+    fn from(value: ScaledPositiveDecimal<SCALE>) -> Self {
+        value.get_unsigned().into()
+    }
+}
+
+impl<const SCALE: u32> TryFrom<ScaledUnsignedDecimal<SCALE>> for ScaledPositiveDecimal<SCALE> {
+    type Error = Error;
+
+    /// Fails with [Error::Zero] for 0.
+    fn try_from(value: ScaledUnsignedDecimal<SCALE>) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl<const SCALE: u32> TryFrom<ScaledSignedDecimal<SCALE>> for ScaledUnsignedDecimal<SCALE> {
+    type Error = Error;
+
+    /// Fails with [Error::Underflow] for negative values.
+    fn try_from(value: ScaledSignedDecimal<SCALE>) -> Result<Self, Self::Error> {
+        if value.is_negative() {
+            Err(Error::Underflow)
+        } else {
+            Ok(value.get_raw_value())
+        }
+    }
+}
+
+impl<const SCALE: u32> TryFrom<ScaledSignedDecimal<SCALE>> for ScaledPositiveDecimal<SCALE> {
+    type Error = Error;
+
+    /// Fails with [Error::Underflow] for negative values and [Error::Zero]
+    /// for 0.
+    fn try_from(value: ScaledSignedDecimal<SCALE>) -> Result<Self, Self::Error> {
+        ScaledUnsignedDecimal::try_from(value).and_then(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use crate::{PositiveDecimal, ScaledUnsignedDecimal, SignedDecimal, UnsignedDecimal};
+
+    use super::*;
+
+    fn u(s: &str) -> UnsignedDecimal {
+        UnsignedDecimal::from_str(s).unwrap()
+    }
+
+    fn s(s: &str) -> SignedDecimal {
+        SignedDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_raw_units() {
+        assert_eq!(UnsignedDecimal::from_raw_units(1_500_000), u("1.5"));
+        assert_eq!(u("1.5").to_raw_units(), 1_500_000);
+        assert_eq!(PositiveDecimal::from_raw_units(0), Err(Error::Zero));
+        assert_eq!(PositiveDecimal::from_raw_units(1), Ok(PositiveDecimal::MIN));
+        assert_eq!(SignedDecimal::from_raw_units(2, true), s("-0.000002"));
+        assert_eq!(SignedDecimal::from_raw_units(0, true), s("0"));
+        assert!(!SignedDecimal::from_raw_units(0, true).is_negative());
+        assert_eq!(s("-0.000002").to_raw_units(), (2, true));
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(UnsignedDecimal::from(42u8), u("42"));
+        assert_eq!(UnsignedDecimal::from(u64::MAX), u("18446744073709551615"));
+        assert_eq!(SignedDecimal::from(-42i32), s("-42"));
+        assert_eq!(SignedDecimal::from(i64::MIN), s("-9223372036854775808"));
+        assert_eq!(PositiveDecimal::try_from(0u32), Err(Error::Zero));
+        assert_eq!(PositiveDecimal::try_from(1u32), Ok("1".parse().unwrap()));
+
+        assert_eq!(UnsignedDecimal::try_from(7u128), Ok(u("7")));
+        assert_eq!(UnsignedDecimal::try_from(u128::MAX), Err(Error::Overflow));
+        assert_eq!(SignedDecimal::try_from(-7i128), Ok(s("-7")));
+        assert_eq!(SignedDecimal::try_from(i128::MIN), Err(Error::Underflow));
+        assert_eq!(SignedDecimal::try_from(i128::MAX), Err(Error::Overflow));
+
+        // Even u64 fits at the largest scale which allows it.
+        assert_eq!(
+            ScaledUnsignedDecimal::<19>::from(u64::MAX).to_string(),
+            "18446744073709551615"
+        );
+    }
+
+    #[test]
+    fn test_decimal_conversions() {
+        assert_eq!(UnsignedDecimal::try_from(s("1.5")), Ok(u("1.5")));
+        assert_eq!(UnsignedDecimal::try_from(s("-1.5")), Err(Error::Underflow));
+        assert_eq!(PositiveDecimal::try_from(s("0")), Err(Error::Zero));
+        assert_eq!(PositiveDecimal::try_from(u("0")), Err(Error::Zero));
+        let p = PositiveDecimal::try_from(s("2")).unwrap();
+        assert_eq!(UnsignedDecimal::from(p), u("2"));
+        assert_eq!(SignedDecimal::from(p), s("2"));
+    }
+
+    #[test]
+    fn test_from_f64() {
+        use RoundingMode::*;
+
+        assert_eq!(UnsignedDecimal::try_from(1.5), Ok(u("1.5")));
+        assert_eq!(UnsignedDecimal::try_from(0.1), Ok(u("0.1")));
+        assert_eq!(UnsignedDecimal::try_from(-0.0), Ok(u("0")));
+        assert_eq!(UnsignedDecimal::try_from(-1.0), Err(Error::Underflow));
+        assert_eq!(UnsignedDecimal::try_from(f64::NAN), Err(Error::NotFinite));
+        assert_eq!(
+            SignedDecimal::try_from(f64::NEG_INFINITY),
+            Err(Error::NotFinite)
+        );
+        assert_eq!(UnsignedDecimal::try_from(1e40), Err(Error::Overflow));
+        assert_eq!(SignedDecimal::try_from(-1e40), Err(Error::Underflow));
+        assert_eq!(SignedDecimal::try_from(-2.25), Ok(s("-2.25")));
+
+        // 0.1 is slightly above 1/10 as an f64, so rounding up goes further.
+        assert_eq!(UnsignedDecimal::from_f64(0.1, Up), Ok(u("0.100001")));
+        assert_eq!(UnsignedDecimal::from_f64(0.1, Down), Ok(u("0.1")));
+        assert_eq!(SignedDecimal::from_f64(-0.1, Down), Ok(s("-0.100001")));
+        assert_eq!(SignedDecimal::from_f64(-0.1, Up), Ok(s("-0.1")));
+        // 1/128 is exactly halfway between two units.
+        assert_eq!(
+            UnsignedDecimal::from_f64(0.0078125, HalfEven),
+            Ok(u("0.007812"))
+        );
+        assert_eq!(
+            UnsignedDecimal::from_f64(0.0078125, HalfUp),
+            Ok(u("0.007813"))
+        );
+
+        // Tiny values need the divisor to be split.
+        assert_eq!(UnsignedDecimal::from_f64(1e-300, Down), Ok(u("0")));
+        assert_eq!(UnsignedDecimal::from_f64(1e-300, Up), Ok(u("0.000001")));
+        assert_eq!(
+            UnsignedDecimal::from_f64(f64::MIN_POSITIVE / 2.0, HalfEven),
+            Ok(u("0"))
+        );
+        assert_eq!(
+            UnsignedDecimal::from_f64(-1e-300, Down),
+            Err(Error::Underflow)
+        );
+        assert_eq!(UnsignedDecimal::from_f64(-1e-300, Up), Ok(u("0")));
+        assert_eq!(
+            PositiveDecimal::from_f64(1e-300, HalfEven),
+            Err(Error::Zero)
+        );
+    }
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(u("1.5").to_f64_lossy(), 1.5);
+        assert_eq!(u("0.1").to_f64_lossy(), 0.1);
+        assert_eq!(s("-1234.5678").to_f64_lossy(), -1234.5678);
+        assert_eq!(PositiveDecimal::MIN.to_f64_lossy(), 0.000001);
+        // Within one unit in the last place of the nearest f64.
+        let max = UnsignedDecimal::MAX.to_f64_lossy();
+        assert!((max - 3.402823669209385e32).abs() <= 3.402823669209385e32 * f64::EPSILON);
+    }
+}
@@ -0,0 +1,49 @@
+/// Errors produced by parsing and checked arithmetic on the decimal types.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The input did not contain any digits where some were expected.
+    #[error("Expected digits, but none were found")]
+    Empty,
+    /// The input contains a character which is not valid at this position.
+    #[error("Invalid character at position {position}")]
+    InvalidDigit {
+        /// Byte offset of the invalid character within the input.
+        position: usize,
+    },
+    /// The input has more fractional digits than the type supports.
+    #[error("Too many fractional digits, at most {max} are supported")]
+    TooManyFractionDigits {
+        /// The maximum number of fractional digits.
+        max: u32,
+    },
+    /// A signed value was written as `-0`.
+    #[error("Cannot have a negative zero")]
+    NegativeZero,
+    /// The result is larger than the maximum value of the type.
+    #[error("Arithmetic overflow")]
+    Overflow,
+    /// The result is smaller than the minimum value of the type.
+    #[error("Arithmetic underflow")]
+    Underflow,
+    /// Attempted to divide by zero.
+    #[error("Division by zero")]
+    DivisionByZero,
+    /// The value is zero, but the type does not allow zero values.
+    #[error("Value cannot be zero")]
+    Zero,
+    /// The value has more fractional digits than the target scale allows.
+    #[error("Value cannot be represented without losing precision")]
+    PrecisionLoss,
+    /// A float conversion was given NaN or an infinity.
+    #[error("Value is not a finite number")]
+    NotFinite,
+    /// A binary encoding is not valid, see [crate::ScaledSignedDecimal::from_bytes].
+    #[error("Invalid binary encoding")]
+    InvalidEncoding,
+    /// The input does not end with the expected unit, such as `%`.
+    #[error("Expected a value ending in {expected}")]
+    MissingUnit {
+        /// The expected unit.
+        expected: &'static str,
+    },
+}
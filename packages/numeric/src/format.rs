@@ -0,0 +1,239 @@
+//! Shared [Display](core::fmt::Display) implementation for the decimal types.
+
+use core::fmt::{Alignment, Display, Formatter, Write};
+
+use crate::{wide::U256, RoundingMode};
+
+/// How to render the digits of a decimal, the options which [Display]
+/// otherwise takes from the formatter's precision and `+` and `#` flags.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Exactly this many fractional digits, rounding half to even, like
+    /// `{:.2}`.
+    pub precision: Option<usize>,
+    /// Always print a sign, like `{:+}`.
+    pub sign_plus: bool,
+    /// Separate thousands with commas, like `{:#}`.
+    pub thousands: bool,
+}
+
+impl FormatOptions {
+    /// The options given to `f`.
+    pub fn from_formatter(f: &Formatter) -> FormatOptions {
+        FormatOptions {
+            precision: f.precision(),
+            sign_plus: f.sign_plus(),
+            thousands: f.alternate(),
+        }
+    }
+}
+
+/// Renders a decimal with [FormatOptions] instead of the formatter's own
+/// precision and flags. Width, fill and alignment still apply.
+pub struct DecimalDisplay {
+    pub(crate) units: u128,
+    pub(crate) scale: u32,
+    pub(crate) negative: bool,
+    pub(crate) options: FormatOptions,
+}
+
+impl Display for DecimalDisplay {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        fmt_decimal(f, self.units, self.scale, self.negative, self.options)
+    }
+}
+
+/// Format a value of `units` with `scale` fractional digits, honoring the
+/// formatter's width, fill and alignment and the given `options`:
+///
+/// * precision, e.g. `{:.2}`: exactly this many fractional digits, rounding
+///   with [RoundingMode::HalfEven] or padding with zeros. Without a
+///   precision, the shortest exact representation is used.
+/// * width, fill and alignment, e.g. `{:>10}` or `{:*^10}`, right aligned by
+///   default. The `0` flag pads with zeros after the sign.
+/// * `{:+}`: always print a sign. Values which round to zero never get a
+///   minus sign.
+/// * `{:#}`: separate thousands in the whole part with commas.
+pub(crate) fn fmt_decimal(
+    f: &mut Formatter,
+    units: u128,
+    scale: u32,
+    negative: bool,
+    options: FormatOptions,
+) -> core::fmt::Result {
+    let (units, digits, extra_zeros) = match options.precision {
+        Some(precision) if precision < scale as usize => {
+            let precision = precision as u32;
+            let rounded = U256::from_u128(units)
+                .div_rounded(
+                    10u128.pow(scale - precision),
+                    negative,
+                    RoundingMode::HalfEven,
+                )
+                .expect("Rounding to fewer digits cannot overflow");
+            (rounded, precision, 0)
+        }
+        Some(precision) => (units, scale, precision - scale as usize),
+        None => {
+            let (mut units, mut digits) = (units, scale);
+            while digits > 0 && units % 10 == 0 {
+                units /= 10;
+                digits -= 1;
+            }
+            (units, digits, 0)
+        }
+    };
+    let multiplier = 10u128.pow(digits);
+    let whole = units / multiplier;
+    let fraction = units % multiplier;
+
+    let sign = if negative && units != 0 {
+        "-"
+    } else if options.sign_plus {
+        "+"
+    } else {
+        ""
+    };
+
+    // u128::MAX has 39 digits.
+    let mut whole_digits = [0u8; 39];
+    let mut whole_len = 0;
+    let mut rest = whole;
+    loop {
+        whole_digits[whole_len] = b'0' + (rest % 10) as u8;
+        whole_len += 1;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    let separators = if options.thousands {
+        (whole_len - 1) / 3
+    } else {
+        0
+    };
+    let has_point = digits > 0 || extra_zeros > 0;
+
+    let len = sign.len()
+        + whole_len
+        + separators
+        + usize::from(has_point)
+        + digits as usize
+        + extra_zeros;
+    let padding = f.width().unwrap_or(0).saturating_sub(len);
+    let (before, after) = if f.sign_aware_zero_pad() {
+        (0, 0)
+    } else {
+        match f.align() {
+            Some(Alignment::Left) => (0, padding),
+            Some(Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(Alignment::Right) | None => (padding, 0),
+        }
+    };
+
+    let fill = f.fill();
+    for _ in 0..before {
+        f.write_char(fill)?;
+    }
+    f.write_str(sign)?;
+    if f.sign_aware_zero_pad() {
+        for _ in 0..padding {
+            f.write_char('0')?;
+        }
+    }
+    for i in (0..whole_len).rev() {
+        f.write_char(whole_digits[i] as char)?;
+        if i > 0 && i % 3 == 0 && options.thousands {
+            f.write_char(',')?;
+        }
+    }
+    if has_point {
+        f.write_char('.')?;
+        if digits > 0 {
+            write!(f, "{fraction:0>width$}", width = digits as usize)?;
+        }
+        for _ in 0..extra_zeros {
+            f.write_char('0')?;
+        }
+    }
+    for _ in 0..after {
+        f.write_char(fill)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FormatOptions, PositiveDecimal, SignedDecimal, UnsignedDecimal};
+
+    fn u(s: &str) -> UnsignedDecimal {
+        s.parse().unwrap()
+    }
+
+    fn s(s: &str) -> SignedDecimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_precision() {
+        assert_eq!(format!("{}", u("5.7")), "5.7");
+        assert_eq!(format!("{:.2}", u("5.7")), "5.70");
+        assert_eq!(format!("{:.0}", u("5.7")), "6");
+        assert_eq!(format!("{:.8}", u("5.7")), "5.70000000");
+        assert_eq!(format!("{:.2}", u("100")), "100.00");
+        // Ties round to even.
+        assert_eq!(format!("{:.1}", u("0.25")), "0.2");
+        assert_eq!(format!("{:.1}", u("0.35")), "0.4");
+        assert_eq!(format!("{:.2}", s("-1.005")), "-1.00");
+        assert_eq!(format!("{:.2}", s("-0.001")), "0.00");
+        assert_eq!(
+            format!("{:.2}", UnsignedDecimal::MAX),
+            "340282366920938463463374607431768.21"
+        );
+    }
+
+    #[test]
+    fn test_width_and_sign() {
+        assert_eq!(format!("{:8.2}", u("5.7")), "    5.70");
+        assert_eq!(format!("{:<8.2}|", u("5.7")), "5.70    |");
+        assert_eq!(format!("{:*^9}", u("5.7")), "***5.7***");
+        assert_eq!(format!("{:08.2}", s("-5.7")), "-0005.70");
+        assert_eq!(format!("{:+}", u("5.7")), "+5.7");
+        assert_eq!(format!("{:+}", s("-5.7")), "-5.7");
+        assert_eq!(format!("{:+.1}", s("0")), "+0.0");
+        assert_eq!(format!("{:2}", u("1234.5")), "1234.5");
+        let p: PositiveDecimal = "5.7".parse().unwrap();
+        assert_eq!(format!("{p:>6.2}"), "  5.70");
+    }
+
+    #[test]
+    fn test_thousands() {
+        assert_eq!(format!("{:#}", u("1234567.891")), "1,234,567.891");
+        assert_eq!(format!("{:#.2}", u("999.999")), "1,000.00");
+        assert_eq!(format!("{:#}", u("123")), "123");
+        assert_eq!(format!("{:#}", s("-1000")), "-1,000");
+        assert_eq!(format!("{:>#10}", u("1000")), "     1,000");
+    }
+
+    #[test]
+    fn test_display_with() {
+        let options = FormatOptions {
+            precision: Some(2),
+            sign_plus: true,
+            thousands: true,
+        };
+        // The options replace the formatter's precision and flags.
+        assert_eq!(
+            format!("{:.0}", u("1234.5").display_with(options)),
+            "+1,234.50"
+        );
+        assert_eq!(
+            format!("{:>10}", s("-5.7").display_with(options)),
+            "     -5.70"
+        );
+        assert_eq!(
+            s("-5.7").display_with(FormatOptions::default()).to_string(),
+            "-5.7"
+        );
+    }
+}
@@ -0,0 +1,224 @@
+//! [Sum] and [Product] for the decimal types, plus checked `try_sum` and
+//! `try_product` helpers.
+//!
+//! Like the arithmetic operators, the [Sum] and [Product] impls panic on
+//! overflow. The checked helpers return the first error instead. Either way,
+//! the values are combined in iteration order, so an intermediate result
+//! which is out of range fails even if the final result would fit.
+
+use core::iter::{Product, Sum};
+
+use crate::{Error, ScaledPositiveDecimal, ScaledSignedDecimal, ScaledUnsignedDecimal};
+
+/// Implement [Sum] or [Product] for a type, over both owned and borrowed
+/// items, using an operator which panics on failure.
+macro_rules! impl_fold {
+    ($trait:ident, $method:ident, $ty:ident, $item:ident, $init:expr, |$acc:ident, $x:ident| $op:expr) => {
+        impl<const SCALE: u32> $trait<$item<SCALE>> for $ty<SCALE> {
+            fn $method<I: Iterator<Item = $item<SCALE>>>(iter: I) -> Self {
+                iter.fold($init, |$acc: Self, $x: $item<SCALE>| $op)
+            }
+        }
+
+        impl<'a, const SCALE: u32> $trait<&'a $item<SCALE>> for $ty<SCALE> {
+            fn $method<I: Iterator<Item = &'a $item<SCALE>>>(iter: I) -> Self {
+                iter.copied().$method()
+            }
+        }
+    };
+}
+
+impl_fold!(
+    Sum,
+    sum,
+    ScaledUnsignedDecimal,
+    ScaledUnsignedDecimal,
+    Self::zero(),
+    |x, y| x + y
+);
+impl_fold!(
+    Sum,
+    sum,
+    ScaledUnsignedDecimal,
+    ScaledPositiveDecimal,
+    Self::zero(),
+    |x, y| x + y.get_unsigned()
+);
+impl_fold!(
+    Product,
+    product,
+    ScaledUnsignedDecimal,
+    ScaledUnsignedDecimal,
+    Self::one(),
+    |x, y| x * y
+);
+impl_fold!(
+    Sum,
+    sum,
+    ScaledSignedDecimal,
+    ScaledSignedDecimal,
+    Self::zero(),
+    |x, y| x + y
+);
+impl_fold!(
+    Product,
+    product,
+    ScaledSignedDecimal,
+    ScaledSignedDecimal,
+    Self::one(),
+    |x, y| x * y
+);
+impl_fold!(
+    Product,
+    product,
+    ScaledPositiveDecimal,
+    ScaledPositiveDecimal,
+    Self::one(),
+    |x, y| x
+        .checked_mul(y)
+        .expect("PositiveDecimal: multiplication failed")
+);
+
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    /// Add up all values, failing with [Error::Overflow] instead of
+    /// panicking. Accepts anything convertible, e.g. positive decimals.
+    pub fn try_sum<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<Self>,
+    {
+        iter.into_iter()
+            .try_fold(Self::zero(), |acc, x| acc.checked_add(x.into()))
+    }
+
+    /// Multiply all values, failing with [Error::Overflow] instead of
+    /// panicking. Each step truncates, like [ScaledUnsignedDecimal::checked_mul].
+    pub fn try_product<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<Self>,
+    {
+        iter.into_iter()
+            .try_fold(Self::one(), |acc, x| acc.checked_mul(x.into()))
+    }
+}
+
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    /// Add up all values, failing with [Error::Overflow] or
+    /// [Error::Underflow] instead of panicking.
+    pub fn try_sum<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<Self>,
+    {
+        iter.into_iter()
+            .try_fold(Self::zero(), |acc, x| acc.checked_add(x.into()))
+    }
+
+    /// Multiply all values, failing instead of panicking. Each step
+    /// truncates, like [ScaledSignedDecimal::checked_mul].
+    pub fn try_product<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator,
+        I::Item: Into<Self>,
+    {
+        iter.into_iter()
+            .try_fold(Self::one(), |acc, x| acc.checked_mul(x.into()))
+    }
+}
+
+impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+    /// Multiply all values, failing with [Error::Overflow], or [Error::Zero]
+    /// if the product truncates to 0, instead of panicking.
+    pub fn try_product<I>(iter: I) -> Result<Self, Error>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        iter.into_iter()
+            .try_fold(Self::one(), |acc, x| acc.checked_mul(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{dec, pdec, PositiveDecimal, SignedDecimal, UnsignedDecimal};
+
+    use super::*;
+
+    fn s(s: &str) -> SignedDecimal {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_sum() {
+        let values = [dec!(1.5), dec!(2.25), dec!(0.000001)];
+        assert_eq!(values.iter().sum::<UnsignedDecimal>(), dec!(3.750001));
+        assert_eq!(values.into_iter().sum::<UnsignedDecimal>(), dec!(3.750001));
+        assert_eq!(
+            [pdec!(1), pdec!(2)].iter().sum::<UnsignedDecimal>(),
+            dec!(3)
+        );
+        assert_eq!(
+            [s("1.5"), s("-4")].into_iter().sum::<SignedDecimal>(),
+            s("-2.5")
+        );
+        assert_eq!(
+            core::iter::empty::<UnsignedDecimal>().sum::<UnsignedDecimal>(),
+            UnsignedDecimal::zero()
+        );
+    }
+
+    #[test]
+    fn test_product() {
+        assert_eq!(
+            [dec!(1.5), dec!(4)].iter().product::<UnsignedDecimal>(),
+            dec!(6)
+        );
+        assert_eq!(
+            [s("-1.5"), s("-2"), s("-1")]
+                .into_iter()
+                .product::<SignedDecimal>(),
+            s("-3")
+        );
+        assert_eq!(
+            core::iter::empty::<PositiveDecimal>().product::<PositiveDecimal>(),
+            pdec!(1)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "addition overflowed")]
+    fn test_sum_panics() {
+        let _ = [UnsignedDecimal::MAX, dec!(0.000001)]
+            .into_iter()
+            .sum::<UnsignedDecimal>();
+    }
+
+    #[test]
+    fn test_try_sum() {
+        assert_eq!(UnsignedDecimal::try_sum([dec!(1), dec!(2)]), Ok(dec!(3)));
+        assert_eq!(UnsignedDecimal::try_sum([pdec!(1), pdec!(2)]), Ok(dec!(3)));
+        assert_eq!(
+            UnsignedDecimal::try_sum([UnsignedDecimal::MAX, dec!(0.000001)]),
+            Err(Error::Overflow)
+        );
+        assert_eq!(
+            SignedDecimal::try_sum([SignedDecimal::MIN, s("-0.000001")]),
+            Err(Error::Underflow)
+        );
+        assert_eq!(SignedDecimal::try_sum([dec!(1), dec!(2)]), Ok(s("3")));
+    }
+
+    #[test]
+    fn test_try_product() {
+        assert_eq!(
+            UnsignedDecimal::try_product([UnsignedDecimal::MAX, dec!(2)]),
+            Err(Error::Overflow)
+        );
+        assert_eq!(
+            PositiveDecimal::try_product([pdec!(0.001), pdec!(0.0001)]),
+            Err(Error::Zero)
+        );
+        assert_eq!(SignedDecimal::try_product([s("-2"), s("3")]), Ok(s("-6")));
+    }
+}
@@ -1,7 +1,76 @@
-pub use positive_decimal::PositiveDecimal;
-pub use signed_decimal::SignedDecimal;
-pub use unsigned_decimal::UnsignedDecimal;
+//! Fixed-point decimal types for asset amounts and prices.
+//!
+//! The crate is `no_std` compatible. Cargo features:
+//!
+//! * `std` (default): implement [std::error::Error] via the standard library,
+//!   and enable `alloc`.
+//! * `alloc`: helpers which need to allocate strings, currently
+//!   [serde::number] when combined with `serde`.
+//! * `serde`: [serde] support.
+//! * `borsh`: [borsh](::borsh) support, which also enables `alloc`.
+//! * `bincode`: [bincode](::bincode) 2 support.
+//! * `proptest`: [proptest] strategies for every type, see [mod@proptest].
+//! * `arbitrary`: [arbitrary](::arbitrary) support, e.g. for fuzzing.
+//!
+//! # Binary encoding
+//!
+//! The decimal types have a compact binary encoding, available through
+//! `to_bytes` and `from_bytes` and used by the `borsh` and `bincode`
+//! features. Unsigned and positive decimals are encoded as their raw number
+//! of units, as a 16 byte little-endian `u128`. Signed decimals add a 17th
+//! byte for the sign: 0 for non-negative values and 1 for negative values.
+//!
+//! The encoding is canonical: every value has exactly one encoding, and
+//! decoding rejects anything else. The scale is not part of the encoding, so
+//! values must be decoded with the same scale they were encoded with.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+pub use error::Error;
+pub use format::{DecimalDisplay, FormatOptions};
+pub use parse::ParseMode;
+pub use positive_decimal::{PositiveDecimal, ScaledPositiveDecimal};
+pub use ratio::{BasisPoints, Percent, Ratio};
+pub use rational::Rational;
+pub use rounding::RoundingMode;
+pub use signed_decimal::{ScaledSignedDecimal, SignedDecimal};
+pub use unsigned_decimal::{ScaledUnsignedDecimal, UnsignedDecimal};
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "bincode")]
+mod bincode;
+#[cfg(feature = "borsh")]
+mod borsh;
+mod bytes;
+mod convert;
+mod error;
+mod format;
+mod iter;
+mod literal;
+mod math;
+pub mod parse;
 mod positive_decimal;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+mod ratio;
+mod rational;
+mod rounding;
+#[cfg(feature = "serde")]
+pub mod serde;
 mod signed_decimal;
 mod unsigned_decimal;
+mod wide;
+
+/// Number of decimal digits used by [UnsignedDecimal], [PositiveDecimal] and
+/// [SignedDecimal].
+pub const DEFAULT_SCALE: u32 = 6;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::literal::{positive, unsigned};
+    pub use macros::{dec, pdec};
+}
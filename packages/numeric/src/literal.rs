@@ -0,0 +1,91 @@
+//! Decimal literals checked at compile time.
+
+use crate::{PositiveDecimal, ScaledPositiveDecimal, UnsignedDecimal, DEFAULT_SCALE};
+
+/// An [UnsignedDecimal] literal, checked at compile time.
+///
+/// ```
+/// use numeric::{dec, UnsignedDecimal};
+///
+/// const FEE: UnsignedDecimal = dec!(0.003);
+/// assert_eq!(FEE.to_string(), "0.003");
+/// assert_eq!(dec!(1_000), "1000".parse().unwrap());
+/// ```
+///
+/// Literals which cannot be represented fail to compile:
+///
+/// ```compile_fail
+/// numeric::dec!(0.0000001);
+/// ```
+#[macro_export]
+macro_rules! dec {
+    ($($lit:tt)+) => {
+        $crate::__private::dec!($crate; $($lit)+)
+    };
+}
+
+/// A [PositiveDecimal] literal, checked at compile time.
+///
+/// ```
+/// use numeric::{pdec, PositiveDecimal};
+///
+/// const MIN_TRADE: PositiveDecimal = pdec!(0.5);
+/// assert_eq!(MIN_TRADE.to_string(), "0.5");
+/// ```
+///
+/// The value 0 fails to compile:
+///
+/// ```compile_fail
+/// numeric::pdec!(0);
+/// ```
+#[macro_export]
+macro_rules! pdec {
+    ($($lit:tt)+) => {
+        $crate::__private::pdec!($crate; $($lit)+)
+    };
+}
+
+/// Build an [UnsignedDecimal] from the parts of a literal, panicking if it
+/// cannot be represented. Called in a const block by [dec!].
+pub const fn unsigned(whole: u128, fraction: u128, fraction_digits: u32) -> UnsignedDecimal {
+    if fraction_digits > DEFAULT_SCALE {
+        panic!("Decimal literal has too many fractional digits");
+    }
+    let fraction = fraction * 10u128.pow(DEFAULT_SCALE - fraction_digits);
+    let raw = match whole.checked_mul(UnsignedDecimal::MULTIPLIER) {
+        Some(raw) => raw.checked_add(fraction),
+        None => None,
+    };
+    match raw {
+        Some(raw) => UnsignedDecimal::from_raw_value(raw),
+        None => panic!("Decimal literal is too large"),
+    }
+}
+
+/// Build a [PositiveDecimal] from the parts of a literal, panicking if it is
+/// 0 or cannot be represented. Called in a const block by [pdec!].
+pub const fn positive(whole: u128, fraction: u128, fraction_digits: u32) -> PositiveDecimal {
+    ScaledPositiveDecimal::new_const(unsigned(whole, fraction, fraction_digits))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PositiveDecimal, UnsignedDecimal};
+
+    #[test]
+    fn test_literals() {
+        const X: UnsignedDecimal = dec!(1.5);
+        assert_eq!(X, "1.5".parse().unwrap());
+        assert_eq!(dec!(0), UnsignedDecimal::zero());
+        assert_eq!(dec!(0.000001), "0.000001".parse().unwrap());
+        assert_eq!(dec!(1_000.25), "1000.25".parse().unwrap());
+        assert_eq!(dec!(2.), "2".parse().unwrap());
+        assert_eq!(
+            dec!(340282366920938463463374607431768.211455),
+            UnsignedDecimal::MAX
+        );
+        const Y: PositiveDecimal = pdec!(2);
+        assert_eq!(Y, "2".parse().unwrap());
+        assert_eq!(pdec!(0.000001), PositiveDecimal::MIN);
+    }
+}
@@ -0,0 +1,478 @@
+//! Square roots, integer powers and fixed-point `exp`/`ln` approximations.
+
+use crate::{
+    wide::U256, Error, RoundingMode, ScaledPositiveDecimal, ScaledSignedDecimal,
+    ScaledUnsignedDecimal,
+};
+
+/// Internal fixed-point precision used by [ScaledUnsignedDecimal::exp] and
+/// [ScaledPositiveDecimal::ln]: 36 fractional digits.
+const FIXED_ONE: u128 = 10u128.pow(36);
+/// ln(2) with 36 fractional digits, truncated.
+const LN_2: u128 = 693_147_180_559_945_309_417_232_121_458_176_568;
+/// ln(10) with 36 fractional digits, truncated.
+const LN_10: u128 = 2_302_585_092_994_045_684_017_991_454_684_364_207;
+
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    /// Square root, computed by Newton's method and rounded according to
+    /// `mode`.
+    ///
+    /// The result is the exact square root rounded once, so it is always
+    /// within one unit in the last place.
+    pub fn sqrt(self, mode: RoundingMode) -> Self {
+        // sqrt(x / M) * M = sqrt(x * M)
+        let n = U256::mul_u128(self.get_raw_value(), Self::MULTIPLIER);
+        let root = n.isqrt();
+        let square = U256::mul_u128(root, root);
+        let round_up = match mode {
+            RoundingMode::Down | RoundingMode::TowardZero => false,
+            RoundingMode::Up | RoundingMode::AwayFromZero => square != n,
+            // (root + 1/2)^2 = root^2 + root + 1/4 is never an integer, so
+            // there are no ties to break.
+            RoundingMode::HalfUp | RoundingMode::HalfEven => {
+                n > square.checked_add_u128(root).unwrap()
+            }
+        };
+        // root is at most sqrt(2^128 * 10^38), so this cannot overflow.
+        Self::from_raw_value(root + u128::from(round_up))
+    }
+
+    /// Raise to an integer power by repeated squaring.
+    ///
+    /// Intermediate results keep about 36 significant digits, and the result
+    /// is rounded to nearest ([RoundingMode::HalfEven]) once at the end.
+    /// Before that final rounding, the relative error is below
+    /// `exp * 10^-34`.
+    pub fn checked_pow(self, exp: u32) -> Result<Self, Error> {
+        // Stop early once the result is certain not to fit.
+        Float::new(self.get_raw_value(), -i64::from(SCALE))
+            .pow(exp, 39 - i64::from(SCALE))
+            .and_then(|power| power.to_raw(SCALE))
+            .map(Self::from_raw_value)
+            .ok_or(Error::Overflow)
+    }
+
+    /// `1 / self^exp`, taking the reciprocal of the unrounded power so that
+    /// the result is rounded to nearest only once.
+    fn checked_recip_pow(self, exp: u32) -> Result<Self, Error> {
+        if self.get_raw_value() == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        // Once the power is at least 10^(SCALE + 1), its reciprocal is below
+        // half a unit in the last place and rounds to 0.
+        let Some(power) =
+            Float::new(self.get_raw_value(), -i64::from(SCALE)).pow(exp, i64::from(SCALE) + 1)
+        else {
+            return Ok(Self::zero());
+        };
+        // The power may have been flushed to 0 if it is tiny, in which case
+        // the reciprocal is certain not to fit.
+        power
+            .recip()
+            .and_then(|recip| recip.to_raw(SCALE))
+            .map(Self::from_raw_value)
+            .ok_or(Error::Overflow)
+    }
+
+    /// Panicking version of [ScaledUnsignedDecimal::checked_pow].
+    pub fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp)
+            .expect("UnsignedDecimal: pow overflowed")
+    }
+
+    /// The exponential function, `e^self`.
+    ///
+    /// Computed with 36 digits of internal precision: before the final
+    /// rounding to nearest, the relative error is below `10^-33`. For results
+    /// with fewer than 33 significant digits, the result is therefore within
+    /// one unit in the last place.
+    pub fn exp(self) -> Result<Self, Error> {
+        let x = U256::mul_u128(self.get_raw_value(), FIXED_ONE)
+            .div_rem_u128(Self::MULTIPLIER)
+            .0
+            .to_u128()
+            .ok_or(Error::Overflow)?;
+        // e^x = 2^k * e^r, with 0 <= r < ln(2)
+        let k = x / LN_2;
+        if k >= 256 {
+            return Err(Error::Overflow);
+        }
+        let r = x - k * LN_2;
+        U256::from_u128(exp_fixed(r))
+            .checked_shl(k as u32)
+            .and_then(|n| n.checked_mul_u128(Self::MULTIPLIER))
+            .and_then(|n| n.div_rounded(FIXED_ONE, false, RoundingMode::HalfEven))
+            .map(Self::from_raw_value)
+            .ok_or(Error::Overflow)
+    }
+
+    /// The natural logarithm, see [ScaledPositiveDecimal::ln].
+    ///
+    /// Fails with [Error::Zero] for an input of 0.
+    pub fn ln(self) -> Result<ScaledSignedDecimal<SCALE>, Error> {
+        ScaledPositiveDecimal::new(self).and_then(ScaledPositiveDecimal::ln)
+    }
+}
+
+impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+    /// See [ScaledUnsignedDecimal::sqrt].
+    pub fn sqrt(self, mode: RoundingMode) -> Self {
+        // The smallest possible input, 10^-SCALE, has a root of at least
+        // 10^-SCALE, so this can never be 0.
+        Self::new(self.get_unsigned().sqrt(mode)).unwrap()
+    }
+
+    /// See [ScaledUnsignedDecimal::checked_pow]. Fails with [Error::Zero] if
+    /// the result rounds to 0.
+    pub fn checked_pow(self, exp: u32) -> Result<Self, Error> {
+        self.get_unsigned().checked_pow(exp).and_then(Self::new)
+    }
+
+    /// Panicking version of [ScaledPositiveDecimal::checked_pow].
+    pub fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).expect("PositiveDecimal: pow failed")
+    }
+
+    /// See [ScaledUnsignedDecimal::exp].
+    pub fn exp(self) -> Result<Self, Error> {
+        self.get_unsigned().exp().and_then(Self::new)
+    }
+
+    /// The natural logarithm.
+    ///
+    /// Computed with 36 digits of internal precision: before the final
+    /// rounding to nearest, the absolute error is below `10^-33`. For scales
+    /// of up to 32 digits, the result is therefore within one unit in the
+    /// last place.
+    ///
+    /// Fails with [Error::Overflow] if the result does not fit, which is
+    /// only possible for scales of 37 digits and above.
+    pub fn ln(self) -> Result<ScaledSignedDecimal<SCALE>, Error> {
+        // ln(raw / 10^SCALE) = ln(raw) - SCALE * ln(10), and
+        // ln(raw) = k * ln(2) + ln(y) with raw = 2^k * y and 1 <= y < 2.
+        let raw = self.get_unsigned().get_raw_value();
+        let k = 127 - raw.leading_zeros();
+        let y = U256::mul_u128(raw, FIXED_ONE).shr(k).to_u128().unwrap();
+        let ln = (i128::from(k) * LN_2 as i128) + ln_fixed(y) as i128
+            - (i128::from(SCALE) * LN_10 as i128);
+
+        let magnitude = U256::mul_u128(
+            ln.unsigned_abs(),
+            ScaledUnsignedDecimal::<SCALE>::MULTIPLIER,
+        )
+        .div_rounded(FIXED_ONE, ln < 0, RoundingMode::HalfEven)
+        .ok_or(Error::Overflow)?;
+        Ok(ScaledSignedDecimal::from_raw_value(
+            ScaledUnsignedDecimal::from_raw_value(magnitude),
+            ln < 0,
+        ))
+    }
+}
+
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    /// Raise to a possibly negative integer power.
+    ///
+    /// See [ScaledUnsignedDecimal::checked_pow] for the rounding behavior. A
+    /// negative exponent takes the reciprocal of the unrounded positive power
+    /// and rounds it to nearest once, with a relative error below
+    /// `(|exp| + 1) * 10^-34` before that rounding. It fails with
+    /// [Error::DivisionByZero] for a base of 0.
+    pub fn checked_powi(self, exp: i32) -> Result<Self, Error> {
+        let negative = self.is_negative() && exp % 2 != 0;
+        let magnitude = if exp < 0 {
+            self.unsigned_abs().checked_recip_pow(exp.unsigned_abs())
+        } else {
+            self.unsigned_abs().checked_pow(exp.unsigned_abs())
+        };
+        match magnitude {
+            Ok(magnitude) => Ok(Self::from_raw_value(magnitude, negative)),
+            Err(Error::Overflow) if negative => Err(Error::Underflow),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Panicking version of [ScaledSignedDecimal::checked_powi].
+    pub fn powi(self, exp: i32) -> Self {
+        self.checked_powi(exp).expect("SignedDecimal: powi failed")
+    }
+}
+
+/// A minimal decimal floating point value, `mantissa * 10^exponent`, used for
+/// the intermediate results of [ScaledUnsignedDecimal::checked_pow].
+#[derive(Clone, Copy)]
+struct Float {
+    mantissa: u128,
+    exponent: i64,
+}
+
+impl Float {
+    /// Mantissas are kept below 2^120, so that products fit in a U256 and
+    /// can be scaled by a power of 10 without overflowing.
+    const MANTISSA_BITS: u32 = 120;
+    /// Values with smaller exponents are treated as 0.
+    const MIN_EXPONENT: i64 = -1000;
+
+    fn new(mantissa: u128, exponent: i64) -> Self {
+        Float::normalize(U256::from_u128(mantissa), exponent)
+    }
+
+    /// Drop decimal digits, truncating, until the mantissa fits.
+    fn normalize(mantissa: U256, exponent: i64) -> Self {
+        let excess_bits = mantissa.bit_len().saturating_sub(Float::MANTISSA_BITS);
+        // Dropping ceil(excess_bits * log10(2)) digits is always enough, using
+        // a slight overestimate of log10(2).
+        let digits = (excess_bits * 30_103).div_ceil(100_000);
+        let (mantissa, _) = mantissa.div_rem_u128(10u128.pow(digits));
+        let mut float = Float {
+            mantissa: mantissa.to_u128().unwrap(),
+            exponent: exponent + i64::from(digits),
+        };
+        if float.mantissa == 0 || float.exponent < Float::MIN_EXPONENT {
+            float = Float {
+                mantissa: 0,
+                exponent: 0,
+            };
+        }
+        float
+    }
+
+    fn mul(self, rhs: Float) -> Float {
+        Float::normalize(
+            U256::mul_u128(self.mantissa, rhs.mantissa),
+            self.exponent + rhs.exponent,
+        )
+    }
+
+    /// Raise to an integer power by repeated squaring, returning [None] as
+    /// soon as the exponent of the result reaches `max_exponent`. Since the
+    /// result only grows for values of at least 1, it is then certain to be
+    /// at least `10^max_exponent`.
+    fn pow(self, exp: u32, max_exponent: i64) -> Option<Float> {
+        let mut base = self;
+        let mut acc = Float::new(1, 0);
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = acc.mul(base);
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.mul(base);
+            }
+            if acc.exponent >= max_exponent {
+                return None;
+            }
+        }
+        Some(acc)
+    }
+
+    /// The reciprocal, keeping about 36 significant digits. Returns [None]
+    /// for 0.
+    fn recip(self) -> Option<Float> {
+        if self.mantissa == 0 {
+            return None;
+        }
+        // Mantissas are below 2^120, so the quotient has at least 36 digits.
+        let (quotient, _) = U256::mul_u128(FIXED_ONE, FIXED_ONE).div_rem_u128(self.mantissa);
+        Some(Float::normalize(quotient, -72 - self.exponent))
+    }
+
+    /// Convert to raw units with `scale` fractional digits, rounding to
+    /// nearest. Returns [None] on overflow.
+    fn to_raw(self, scale: u32) -> Option<u128> {
+        let exponent = self.exponent + i64::from(scale);
+        if exponent >= 0 {
+            let exponent = u32::try_from(exponent).ok()?;
+            self.mantissa.checked_mul(10u128.checked_pow(exponent)?)
+        } else if exponent < -38 {
+            // The mantissa is below 10^37, so this rounds to 0.
+            Some(0)
+        } else {
+            U256::from_u128(self.mantissa).div_rounded(
+                10u128.pow(exponent.unsigned_abs() as u32),
+                false,
+                RoundingMode::HalfEven,
+            )
+        }
+    }
+}
+
+/// Multiply two fixed-point values, truncating.
+fn mul_fixed(x: u128, y: u128) -> u128 {
+    U256::mul_u128(x, y)
+        .div_rem_u128(FIXED_ONE)
+        .0
+        .to_u128()
+        .expect("mul_fixed: only used on values below 2")
+}
+
+/// e^r for a fixed-point `0 <= r < 1`, using its Taylor series.
+fn exp_fixed(r: u128) -> u128 {
+    let mut sum = FIXED_ONE;
+    let mut term = FIXED_ONE;
+    let mut n = 1;
+    loop {
+        term = mul_fixed(term, r) / n;
+        if term == 0 {
+            break sum;
+        }
+        sum += term;
+        n += 1;
+    }
+}
+
+/// ln(y) for a fixed-point `1 <= y < 2`, using the series
+/// `ln(y) = 2 * atanh(z)` with `z = (y - 1) / (y + 1)`.
+fn ln_fixed(y: u128) -> u128 {
+    let z = U256::mul_u128(y - FIXED_ONE, FIXED_ONE)
+        .div_rem_u128(y + FIXED_ONE)
+        .0
+        .to_u128()
+        .unwrap();
+    let z2 = mul_fixed(z, z);
+    let mut sum = z;
+    let mut term = z;
+    let mut n = 3;
+    loop {
+        term = mul_fixed(term, z2);
+        if term == 0 {
+            break sum * 2;
+        }
+        sum += term / n;
+        n += 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::str::FromStr;
+
+    use crate::{PositiveDecimal, SignedDecimal, UnsignedDecimal};
+
+    use super::*;
+
+    fn u(s: &str) -> UnsignedDecimal {
+        UnsignedDecimal::from_str(s).unwrap()
+    }
+
+    fn p(s: &str) -> PositiveDecimal {
+        PositiveDecimal::from_str(s).unwrap()
+    }
+
+    fn s(s: &str) -> SignedDecimal {
+        SignedDecimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(u("4").sqrt(RoundingMode::Down), u("2"));
+        assert_eq!(u("0").sqrt(RoundingMode::Up), u("0"));
+        // sqrt(2) = 1.414213|56...
+        assert_eq!(u("2").sqrt(RoundingMode::Down), u("1.414213"));
+        assert_eq!(u("2").sqrt(RoundingMode::Up), u("1.414214"));
+        assert_eq!(u("2").sqrt(RoundingMode::HalfEven), u("1.414214"));
+        assert_eq!(u("0.000001").sqrt(RoundingMode::Down), u("0.001"));
+        assert_eq!(p("0.000001").sqrt(RoundingMode::Down), p("0.001"));
+
+        // LP share issuance, sqrt(x * y), for a large pool
+        let x = u("103000000000000");
+        let y = u("100000000000000");
+        assert_eq!(
+            x.mul_rounded(y, RoundingMode::Down)
+                .unwrap()
+                .sqrt(RoundingMode::Down),
+            u("101488915650922.194686")
+        );
+
+        let max = UnsignedDecimal::MAX.sqrt(RoundingMode::Up);
+        assert_eq!(max.to_string(), "18446744073709551.616");
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(u("1.1").pow(2), u("1.21"));
+        assert_eq!(u("2").pow(10), u("1024"));
+        assert_eq!(u("5").pow(0), u("1"));
+        assert_eq!(u("0").pow(0), u("1"));
+        assert_eq!(u("10").checked_pow(40), Err(Error::Overflow));
+        // Compound growth, exactly 1.000001^1000000 = 2.718280|4693...
+        assert_eq!(u("1.000001").pow(1_000_000), u("2.71828"));
+        assert_eq!(u("0.5").pow(u32::MAX), u("0"));
+        assert_eq!(u("1").pow(u32::MAX), u("1"));
+        assert_eq!(u("1.000001").checked_pow(u32::MAX), Err(Error::Overflow));
+        assert_eq!(u("1000000.5").pow(2), u("1000001000000.25"));
+        // Exactly 340282366920938463426481119284349.108225, but only about
+        // 36 significant digits are kept.
+        let square = u("18446744073709551.615").pow(2);
+        let exact = u("340282366920938463426481119284349.108225");
+        assert!((square - exact).unsigned_abs() <= u("0.001"));
+        assert_eq!(p("0.001").checked_pow(3), Err(Error::Zero));
+        assert_eq!(p("0.1").pow(3), p("0.001"));
+    }
+
+    #[test]
+    fn test_powi() {
+        assert_eq!(s("-2").powi(3), s("-8"));
+        assert_eq!(s("-2").powi(2), s("4"));
+        assert_eq!(s("-2").powi(-3), s("-0.125"));
+        assert_eq!(s("3").powi(-1), s("0.333333"));
+        assert_eq!(s("0").checked_powi(-1), Err(Error::DivisionByZero));
+        assert_eq!(s("-10").checked_powi(41), Err(Error::Underflow));
+        assert_eq!(s("10").powi(-41), s("0"));
+        // The reciprocal is taken before rounding the power to 6 digits.
+        assert_eq!(s("0.001").powi(-3), s("1000000000"));
+        assert_eq!(s("0.0015").powi(-2), s("444444.444444"));
+        assert_eq!(s("-0.000001").powi(-3), s("-1000000000000000000"));
+        assert_eq!(s("0.000001").checked_powi(-7), Err(Error::Overflow));
+        assert_eq!(
+            ScaledSignedDecimal::<38>::from_str("2").unwrap().powi(-1),
+            ScaledSignedDecimal::<38>::from_str("0.5").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_exp() {
+        assert_eq!(u("0").exp(), Ok(u("1")));
+        assert_eq!(u("1").exp(), Ok(u("2.718282")));
+        // e^20 = 485165195.409790|2779...
+        assert_eq!(u("20").exp(), Ok(u("485165195.40979")));
+        // e^70 = 2515438670919167006265781174252.112961|4...
+        // With 37 significant digits, this is outside the one unit in the
+        // last place guarantee.
+        let e70 = u("70").exp().unwrap();
+        let exact = u("2515438670919167006265781174252.112961");
+        assert!((e70 - exact).unsigned_abs() <= u("0.00001"));
+        assert_eq!(u("75").exp(), Err(Error::Overflow));
+        assert_eq!(u("1000").exp(), Err(Error::Overflow));
+        assert_eq!(p("1").exp(), Ok(p("2.718282")));
+    }
+
+    #[test]
+    fn test_ln() {
+        assert_eq!(p("1").ln(), Ok(s("0")));
+        assert_eq!(p("2").ln(), Ok(s("0.693147")));
+        assert_eq!(p("0.5").ln(), Ok(s("-0.693147")));
+        assert_eq!(p("10").ln(), Ok(s("2.302585")));
+        assert_eq!(p("12.345").ln(), Ok(s("2.513251")));
+        assert_eq!(p("0.000001").ln(), Ok(s("-13.815511")));
+        assert_eq!(u("0").ln(), Err(Error::Zero));
+        assert_eq!(PositiveDecimal::MAX.ln(), Ok(s("74.907329")));
+
+        // |ln| * 10^38 does not fit for values far from 1.
+        type Wide = ScaledPositiveDecimal<38>;
+        assert_eq!(Wide::MIN.ln(), Err(Error::Overflow));
+        assert!(Wide::MAX.ln().is_ok());
+    }
+
+    #[test]
+    fn test_exp_ln_round_trip() {
+        // ln(x) is rounded to 6 digits, so exp(ln(x)) is only accurate to
+        // a relative error of about 5 * 10^-7.
+        for x in ["1", "1.5", "3.25", "20", "123.456789"] {
+            let x = u(x);
+            let y = x.ln().unwrap().unsigned_abs().exp().unwrap();
+            let diff = (y - x).unsigned_abs();
+            assert!(diff <= x * u("0.000001"), "{x} {y}");
+        }
+    }
+}
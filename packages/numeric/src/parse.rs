@@ -0,0 +1,320 @@
+//! Parsing decimal strings.
+//!
+//! All decimal types share a single grammar, in two modes. The
+//! [ParseMode::Lenient] grammar, intended for user input, is:
+//!
+//! ```text
+//! decimal     = [sign] significand [exponent]
+//! sign        = "+" | "-"
+//! significand = digits ["." [digits]]
+//!             | "." digits
+//! digits      = digit {["_"] digit}
+//! exponent    = ("e" | "E") [sign] digit {digit}
+//! ```
+//!
+//! For example `+1_000.25`, `.5`, `5.` and `1.5e3` are all valid. Values
+//! must be represented exactly: `1.5e-7` is rejected with six fractional
+//! digits, but `1.0000000` and `100e-2` are accepted.
+//!
+//! The [ParseMode::Strict] grammar, intended for wire formats, only accepts
+//! a minus sign, plain digits, and a fraction with at least one digit on each
+//! side of the point. It accepts everything [Display](core::fmt::Display)
+//! produces:
+//!
+//! ```text
+//! decimal = ["-"] digit {digit} ["." digit {digit}]
+//! ```
+//!
+//! In both modes, unsigned types reject any `-` sign and signed types
+//! reject `-0`. [FromStr](core::str::FromStr) uses [ParseMode::Lenient].
+
+use crate::Error;
+
+/// Which grammar to accept when parsing, see the [module docs](self).
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub enum ParseMode {
+    /// Only the canonical format, for wire formats.
+    Strict,
+    /// Also accept `+`, digit separators, missing digits around the point
+    /// and exponents, for user input.
+    #[default]
+    Lenient,
+}
+
+/// Split `s` into the longest prefix which looks like a decimal in the
+/// given mode, and the rest of the string.
+///
+/// The prefix may still fail to parse, e.g. because of a missing digit or a
+/// value which is out of range. This is used to split strings such as
+/// `12.5USD`, where an asset symbol follows the number. Note that an `e`
+/// is only treated as an exponent when followed by digits, so `1EURO` is
+/// split as `1` and `EURO`.
+pub fn split_decimal(s: &str, mode: ParseMode) -> (&str, &str) {
+    s.split_at(scan(s, mode).end)
+}
+
+/// Like [split_decimal], for a [Rational](crate::Rational) such as `11/10`
+/// followed by other text. A slash is only part of the fraction when
+/// followed by a digit, so `2USD/EURO` is split as `2` and `USD/EURO`.
+pub fn split_rational(s: &str, mode: ParseMode) -> (&str, &str) {
+    let (numer, rest) = split_decimal(s, mode);
+    let end = match rest.strip_prefix('/') {
+        Some(denom) if denom.starts_with(|c: char| c.is_ascii_digit()) => {
+            numer.len() + 1 + split_decimal(denom, mode).0.len()
+        }
+        _ => numer.len(),
+    };
+    s.split_at(end)
+}
+
+/// The pieces of a decimal string, after scanning.
+pub(crate) struct Number<'a> {
+    pub(crate) negative: bool,
+    /// Digits before the point, possibly including separators.
+    whole: &'a str,
+    /// Digits after the point, possibly including separators.
+    fraction: &'a str,
+    /// Whether the string contained a decimal point.
+    has_point: bool,
+    /// Exponent, saturated to a range much larger than any valid value.
+    exponent: i64,
+    /// Byte length of the scanned prefix.
+    end: usize,
+}
+
+/// Scan the longest prefix of `s` matching the grammar, without checking
+/// that digits are present where required.
+fn scan(s: &str, mode: ParseMode) -> Number<'_> {
+    let bytes = s.as_bytes();
+    let lenient = mode == ParseMode::Lenient;
+    let mut pos = 0;
+
+    let negative = match bytes.first() {
+        Some(b'-') => {
+            pos += 1;
+            true
+        }
+        Some(b'+') if lenient => {
+            pos += 1;
+            false
+        }
+        _ => false,
+    };
+
+    let whole_start = pos;
+    pos = scan_digits(bytes, pos, lenient);
+    let whole = &s[whole_start..pos];
+
+    let has_point = bytes.get(pos) == Some(&b'.');
+    let fraction = if has_point {
+        pos += 1;
+        let fraction_start = pos;
+        pos = scan_digits(bytes, pos, lenient);
+        &s[fraction_start..pos]
+    } else {
+        ""
+    };
+
+    let mut exponent = 0;
+    if lenient && matches!(bytes.get(pos), Some(b'e' | b'E')) {
+        let mut exp_pos = pos + 1;
+        let exp_negative = match bytes.get(exp_pos) {
+            Some(b'-') => {
+                exp_pos += 1;
+                true
+            }
+            Some(b'+') => {
+                exp_pos += 1;
+                false
+            }
+            _ => false,
+        };
+        let digits_start = exp_pos;
+        while bytes.get(exp_pos).is_some_and(u8::is_ascii_digit) {
+            exponent = (exponent * 10 + i64::from(bytes[exp_pos] - b'0')).min(1_000_000);
+            exp_pos += 1;
+        }
+        // Without digits, the e is not part of the number.
+        if exp_pos > digits_start {
+            pos = exp_pos;
+            if exp_negative {
+                exponent = -exponent;
+            }
+        } else {
+            exponent = 0;
+        }
+    }
+
+    Number {
+        negative,
+        whole,
+        fraction,
+        has_point,
+        exponent,
+        end: pos,
+    }
+}
+
+/// Scan digits starting at `pos`, allowing single underscores between
+/// digits in lenient mode. Returns the end position.
+fn scan_digits(bytes: &[u8], mut pos: usize, lenient: bool) -> usize {
+    let start = pos;
+    loop {
+        match bytes.get(pos) {
+            Some(b) if b.is_ascii_digit() => pos += 1,
+            Some(b'_')
+                if lenient && pos > start && bytes.get(pos + 1).is_some_and(u8::is_ascii_digit) =>
+            {
+                pos += 1
+            }
+            _ => return pos,
+        }
+    }
+}
+
+impl<'a> Number<'a> {
+    /// Parse a complete string.
+    pub(crate) fn parse(s: &'a str, mode: ParseMode) -> Result<Self, Error> {
+        let number = scan(s, mode);
+        if number.end < s.len() {
+            return Err(Error::InvalidDigit {
+                position: number.end,
+            });
+        }
+        let has_digits = match mode {
+            ParseMode::Strict => {
+                !number.whole.is_empty() && (!number.has_point || !number.fraction.is_empty())
+            }
+            ParseMode::Lenient => !number.whole.is_empty() || !number.fraction.is_empty(),
+        };
+        if has_digits {
+            Ok(number)
+        } else {
+            Err(Error::Empty)
+        }
+    }
+
+    /// The magnitude as a number of units with `scale` fractional digits.
+    ///
+    /// Fails with [Error::TooManyFractionDigits] if the value cannot be
+    /// represented exactly, and [Error::Overflow] if it is too large.
+    pub(crate) fn to_units(&self, scale: u32, mode: ParseMode) -> Result<u128, Error> {
+        let too_many_digits = Error::TooManyFractionDigits { max: scale };
+        let fraction_len = self.fraction.bytes().filter(u8::is_ascii_digit).count();
+        if mode == ParseMode::Strict && fraction_len > scale as usize {
+            return Err(too_many_digits);
+        }
+        // The value is digits * 10^shift, where digits are all the digits
+        // with the point removed.
+        let digits = self
+            .whole
+            .bytes()
+            .chain(self.fraction.bytes())
+            .filter(u8::is_ascii_digit);
+        let shift = i64::from(scale) + self.exponent - fraction_len as i64;
+
+        let mut units = 0u128;
+        // Leading digits to keep. With a negative shift, the remaining digits
+        // are dropped, so they must all be 0.
+        let mut keep = digits.clone().count() as i64 + shift.min(0);
+        for digit in digits {
+            let digit = u128::from(digit - b'0');
+            if keep > 0 {
+                keep -= 1;
+                units = units
+                    .checked_mul(10)
+                    .and_then(|units| units.checked_add(digit))
+                    .ok_or(Error::Overflow)?;
+            } else if digit != 0 {
+                return Err(too_many_digits);
+            }
+        }
+        if units == 0 || shift <= 0 {
+            return Ok(units);
+        }
+        u32::try_from(shift)
+            .ok()
+            .and_then(|shift| 10u128.checked_pow(shift))
+            .and_then(|multiplier| units.checked_mul(multiplier))
+            .ok_or(Error::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn units(s: &str, mode: ParseMode) -> Result<(bool, u128), Error> {
+        let number = Number::parse(s, mode)?;
+        Ok((number.negative, number.to_units(6, mode)?))
+    }
+
+    #[test]
+    fn test_lenient() {
+        let p = |s| units(s, ParseMode::Lenient);
+        assert_eq!(p("1.5"), Ok((false, 1_500_000)));
+        assert_eq!(p("+1.5"), Ok((false, 1_500_000)));
+        assert_eq!(p("-1.5"), Ok((true, 1_500_000)));
+        assert_eq!(p(".5"), Ok((false, 500_000)));
+        assert_eq!(p("5."), Ok((false, 5_000_000)));
+        assert_eq!(p("1_000.000_1"), Ok((false, 1_000_000_100)));
+        assert_eq!(p("1.5e3"), Ok((false, 1_500_000_000)));
+        assert_eq!(p("15E-1"), Ok((false, 1_500_000)));
+        assert_eq!(p("1e+2"), Ok((false, 100_000_000)));
+        assert_eq!(p("1.0000000"), Ok((false, 1_000_000)));
+        assert_eq!(p("100e-8"), Ok((false, 1)));
+        assert_eq!(p("0e999999999999"), Ok((false, 0)));
+        assert_eq!(
+            p("000000000000000000000000000000000000000001"),
+            Ok((false, 1_000_000))
+        );
+
+        assert_eq!(p(""), Err(Error::Empty));
+        assert_eq!(p("."), Err(Error::Empty));
+        assert_eq!(p("+"), Err(Error::Empty));
+        assert_eq!(p("_1"), Err(Error::InvalidDigit { position: 0 }));
+        assert_eq!(p("1_"), Err(Error::InvalidDigit { position: 1 }));
+        assert_eq!(p("1__0"), Err(Error::InvalidDigit { position: 1 }));
+        assert_eq!(p("1_.5"), Err(Error::InvalidDigit { position: 1 }));
+        assert_eq!(p("1e"), Err(Error::InvalidDigit { position: 1 }));
+        assert_eq!(p("1e-"), Err(Error::InvalidDigit { position: 1 }));
+        assert_eq!(p("1e1_0"), Err(Error::InvalidDigit { position: 3 }));
+        assert_eq!(p("1.5e-7"), Err(Error::TooManyFractionDigits { max: 6 }));
+        assert_eq!(p("1e33"), Err(Error::Overflow));
+        assert_eq!(p("1e999999999999"), Err(Error::Overflow));
+    }
+
+    #[test]
+    fn test_strict() {
+        let p = |s| units(s, ParseMode::Strict);
+        assert_eq!(p("1.5"), Ok((false, 1_500_000)));
+        assert_eq!(p("-1.5"), Ok((true, 1_500_000)));
+        assert_eq!(p("0.000001"), Ok((false, 1)));
+
+        assert_eq!(p(".5"), Err(Error::Empty));
+        assert_eq!(p("5."), Err(Error::Empty));
+        assert_eq!(p("+1"), Err(Error::InvalidDigit { position: 0 }));
+        assert_eq!(p("1_000"), Err(Error::InvalidDigit { position: 1 }));
+        assert_eq!(p("1e3"), Err(Error::InvalidDigit { position: 1 }));
+        assert_eq!(p("1.0000000"), Err(Error::TooManyFractionDigits { max: 6 }));
+    }
+
+    #[test]
+    fn test_split() {
+        use ParseMode::*;
+        assert_eq!(split_decimal("12.5USD", Strict), ("12.5", "USD"));
+        assert_eq!(split_decimal("1EURO", Lenient), ("1", "EURO"));
+        assert_eq!(split_decimal("1e3EURO", Lenient), ("1e3", "EURO"));
+        assert_eq!(split_decimal("1e3EURO", Strict), ("1", "e3EURO"));
+        assert_eq!(
+            split_decimal("1_000 USD/EURO", Lenient),
+            ("1_000", " USD/EURO")
+        );
+        assert_eq!(split_decimal("USD", Lenient), ("", "USD"));
+        assert_eq!(
+            split_rational("11/10 USD/EURO", Strict),
+            ("11/10", " USD/EURO")
+        );
+        assert_eq!(split_rational("2USD/EURO", Strict), ("2", "USD/EURO"));
+    }
+}
@@ -1,84 +1,202 @@
-use std::{
+use core::{
     fmt::{Debug, Display},
     str::FromStr,
 };
 
-use anyhow::Result;
-pub use private::PositiveDecimal;
+pub use private::ScaledPositiveDecimal;
 
-mod private {
-    use anyhow::Result;
+use crate::{Error, ParseMode, RoundingMode, ScaledUnsignedDecimal, DEFAULT_SCALE};
 
-    use crate::UnsignedDecimal;
+mod private {
+    use crate::{Error, ScaledUnsignedDecimal};
 
-    /// A version of [UnsignedDecimal] which disallows the value 0.
+    /// A version of [ScaledUnsignedDecimal] which disallows the value 0.
     #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-    pub struct PositiveDecimal {
+    pub struct ScaledPositiveDecimal<const SCALE: u32> {
         // Invariant: can never be 0
-        value: UnsignedDecimal,
+        value: ScaledUnsignedDecimal<SCALE>,
     }
 
-    impl PositiveDecimal {
+    impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+        /// The smallest representable value.
+        pub const MIN: Self = ScaledPositiveDecimal {
+            value: ScaledUnsignedDecimal::from_raw_value(1),
+        };
+
+        /// The largest representable value.
+        pub const MAX: Self = ScaledPositiveDecimal {
+            value: ScaledUnsignedDecimal::MAX,
+        };
+
         /// Generate a new value, checking that the input is not 0.
-        pub fn new(value: UnsignedDecimal) -> Result<Self> {
-            anyhow::ensure!(value.get_raw_value() != 0, "PositiveDecimal cannot be 0");
-            Ok(PositiveDecimal { value })
+        pub fn new(value: ScaledUnsignedDecimal<SCALE>) -> Result<Self, Error> {
+            if value.get_raw_value() == 0 {
+                Err(Error::Zero)
+            } else {
+                Ok(ScaledPositiveDecimal { value })
+            }
+        }
+
+        /// Like [Self::new], but usable in const contexts. Panics on 0.
+        pub(crate) const fn new_const(value: ScaledUnsignedDecimal<SCALE>) -> Self {
+            assert!(value.get_raw_value() != 0, "Positive decimal cannot be 0");
+            ScaledPositiveDecimal { value }
         }
 
         /// Get the raw unsigned value.
-        pub fn get_unsigned(&self) -> UnsignedDecimal {
+        pub const fn get_unsigned(&self) -> ScaledUnsignedDecimal<SCALE> {
             self.value
         }
     }
 }
 
-impl FromStr for PositiveDecimal {
-    type Err = anyhow::Error;
+/// A positive decimal with the default 6 digits of precision.
+pub type PositiveDecimal = ScaledPositiveDecimal<DEFAULT_SCALE>;
+
+impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+    pub fn one() -> Self {
+        Self::new_const(ScaledUnsignedDecimal::one())
+    }
+
+    /// Parse a string using the given grammar, see [crate::parse].
+    pub fn parse_with(s: &str, mode: ParseMode) -> Result<Self, Error> {
+        ScaledUnsignedDecimal::parse_with(s, mode).and_then(Self::new)
+    }
+}
+
+/// Parses with [ParseMode::Lenient].
+impl<const SCALE: u32> FromStr for ScaledPositiveDecimal<SCALE> {
+    type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        s.parse().and_then(PositiveDecimal::new)
+        Self::parse_with(s, ParseMode::Lenient)
     }
 }
 
-impl Display for PositiveDecimal {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.get_unsigned())
+/// Supports the same options as [ScaledUnsignedDecimal].
+impl<const SCALE: u32> Display for ScaledPositiveDecimal<SCALE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        Display::fmt(&self.get_unsigned(), f)
     }
 }
 
-impl Debug for PositiveDecimal {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl<const SCALE: u32> Debug for ScaledPositiveDecimal<SCALE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{self}")
     }
 }
 
-impl std::ops::Add for PositiveDecimal {
-    type Output = PositiveDecimal;
+impl<const SCALE: u32> core::ops::Add for ScaledPositiveDecimal<SCALE> {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        PositiveDecimal::new(self.get_unsigned() + rhs.get_unsigned()).unwrap()
+        self.checked_add(rhs)
+            .expect("PositiveDecimal: addition overflowed")
     }
 }
 
-impl std::ops::AddAssign for PositiveDecimal {
+impl<const SCALE: u32> core::ops::AddAssign for ScaledPositiveDecimal<SCALE> {
     fn add_assign(&mut self, rhs: Self) {
         *self = *self + rhs;
     }
 }
 
-impl PositiveDecimal {
-    pub fn checked_sub(self, rhs: Self) -> Result<Self> {
+impl<const SCALE: u32> core::ops::Div for ScaledPositiveDecimal<SCALE> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs)
+            .expect("PositiveDecimal: division failed")
+    }
+}
+
+/// Checked arithmetic, returning an [Error] instead of panicking.
+///
+/// Besides overflow, any operation whose result is 0 fails with [Error::Zero].
+impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+    pub fn checked_add(self, rhs: Self) -> Result<Self, Error> {
+        self.get_unsigned()
+            .checked_add(rhs.get_unsigned())
+            .and_then(Self::new)
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, Error> {
         self.get_unsigned()
             .checked_sub(rhs.get_unsigned())
-            .and_then(PositiveDecimal::new)
+            .and_then(Self::new)
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, Error> {
+        self.get_unsigned()
+            .checked_mul(rhs.get_unsigned())
+            .and_then(Self::new)
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Result<Self, Error> {
+        self.get_unsigned()
+            .checked_div(rhs.get_unsigned())
+            .and_then(Self::new)
     }
 }
 
-impl std::ops::Div for PositiveDecimal {
-    type Output = PositiveDecimal;
+/// Multiplication and division with an explicit [RoundingMode].
+///
+/// A result which rounds to 0 fails with [Error::Zero].
+impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+    pub fn mul_rounded(self, rhs: Self, mode: RoundingMode) -> Result<Self, Error> {
+        self.get_unsigned()
+            .mul_rounded(rhs.get_unsigned(), mode)
+            .and_then(Self::new)
+    }
 
-    fn div(self, rhs: Self) -> Self::Output {
-        PositiveDecimal::new(self.get_unsigned() / rhs.get_unsigned()).unwrap()
+    pub fn div_rounded(self, rhs: Self, mode: RoundingMode) -> Result<Self, Error> {
+        self.get_unsigned()
+            .div_rounded(rhs.get_unsigned(), mode)
+            .and_then(Self::new)
+    }
+}
+
+/// Saturating arithmetic, clamping the result to the range `MIN..=MAX`.
+///
+/// There are no `overflowing_*` variants, since a wrapped result may be 0.
+impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or(Self::MAX)
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).unwrap_or(Self::MIN)
+    }
+
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs).unwrap_or_else(saturate)
+    }
+
+    pub fn saturating_div(self, rhs: Self) -> Self {
+        self.checked_div(rhs).unwrap_or_else(saturate)
+    }
+}
+
+fn saturate<const SCALE: u32>(e: Error) -> ScaledPositiveDecimal<SCALE> {
+    match e {
+        Error::Overflow => ScaledPositiveDecimal::MAX,
+        _ => ScaledPositiveDecimal::MIN,
+    }
+}
+
+/// Conversions between different scales, see [ScaledUnsignedDecimal::widen]
+/// and [ScaledUnsignedDecimal::narrow].
+impl<const SCALE: u32> ScaledPositiveDecimal<SCALE> {
+    pub fn widen<const TO: u32>(self) -> Result<ScaledPositiveDecimal<TO>, Error> {
+        self.get_unsigned()
+            .widen()
+            .and_then(ScaledPositiveDecimal::new)
+    }
+
+    pub fn narrow<const TO: u32>(self) -> Result<ScaledPositiveDecimal<TO>, Error> {
+        self.get_unsigned()
+            .narrow()
+            .and_then(ScaledPositiveDecimal::new)
     }
 }
 
@@ -96,6 +214,54 @@ mod tests {
         assert_eq!(z, z2);
     }
 
+    #[test]
+    fn test_checked_arithmetic() {
+        let p = |s| PositiveDecimal::from_str(s).unwrap();
+        assert_eq!(
+            PositiveDecimal::MAX.checked_add(PositiveDecimal::MIN),
+            Err(Error::Overflow)
+        );
+        assert_eq!(p("2").checked_sub(p("2")), Err(Error::Zero));
+        assert_eq!(p("2").checked_sub(p("3")), Err(Error::Underflow));
+        assert_eq!(p("0.001").checked_mul(p("0.0001")), Err(Error::Zero));
+        assert_eq!(p("1").checked_div(p("3000000")), Err(Error::Zero));
+        assert_eq!(p("1").checked_div(p("4")), Ok(p("0.25")));
+    }
+
+    #[test]
+    fn test_rounded_arithmetic() {
+        let p = |s| PositiveDecimal::from_str(s).unwrap();
+        let x = p("0.000001");
+        assert_eq!(x.mul_rounded(x, RoundingMode::Down), Err(Error::Zero));
+        assert_eq!(x.mul_rounded(x, RoundingMode::Up), Ok(x));
+        assert_eq!(
+            p("1").div_rounded(p("3"), RoundingMode::HalfUp),
+            Ok(p("0.333333"))
+        );
+    }
+
+    #[test]
+    fn test_saturating_arithmetic() {
+        let p = |s| PositiveDecimal::from_str(s).unwrap();
+        let max = PositiveDecimal::MAX;
+        let min = PositiveDecimal::MIN;
+        assert_eq!(max.saturating_add(p("1")), max);
+        assert_eq!(p("1").saturating_sub(p("2")), min);
+        assert_eq!(max.saturating_mul(p("2")), max);
+        assert_eq!(p("0.001").saturating_mul(p("0.0001")), min);
+        assert_eq!(p("1").saturating_div(p("3000000")), min);
+    }
+
+    #[test]
+    fn test_rescale() {
+        let x: PositiveDecimal = "0.5".parse().unwrap();
+        let wide: ScaledPositiveDecimal<10> = x.widen().unwrap();
+        assert_eq!(wide.to_string(), "0.5");
+        assert_eq!(wide.narrow::<1>().unwrap().to_string(), "0.5");
+        let tiny: ScaledPositiveDecimal<10> = "0.0000000001".parse().unwrap();
+        assert_eq!(tiny.narrow::<6>(), Err(Error::PrecisionLoss));
+    }
+
     #[test]
     fn test_parse() {
         PositiveDecimal::from_str("5.2").unwrap();
@@ -103,5 +269,10 @@ mod tests {
         PositiveDecimal::from_str("-0").unwrap_err();
         PositiveDecimal::from_str("0").unwrap_err();
         PositiveDecimal::from_str("0.0").unwrap_err();
+        assert_eq!(PositiveDecimal::from_str("0.0"), Err(Error::Zero));
+        assert_eq!(
+            PositiveDecimal::from_str("-5"),
+            Err(Error::InvalidDigit { position: 0 })
+        );
     }
 }
@@ -0,0 +1,140 @@
+//! [proptest] strategies, enabled by the `proptest` feature.
+//!
+//! Uniformly random `u128`s are almost always huge, so the strategies here
+//! are biased towards edge cases: 0, the smallest and largest values, small
+//! numbers of units and whole numbers. Every type also implements
+//! [Arbitrary], so `any::<UnsignedDecimal>()` uses the same strategy.
+
+use ::proptest::{
+    arbitrary::Arbitrary,
+    prelude::{any, Just, Strategy},
+    prop_oneof,
+    strategy::BoxedStrategy,
+};
+
+use crate::{
+    BasisPoints, Percent, Ratio, Rational, ScaledPositiveDecimal, ScaledSignedDecimal,
+    ScaledUnsignedDecimal, UnsignedDecimal,
+};
+
+/// Raw numbers of units, biased towards edge cases.
+fn raw_units<const SCALE: u32>() -> impl Strategy<Value = u128> {
+    prop_oneof![
+        Just(0),
+        Just(1),
+        Just(u128::MAX),
+        0..1_000_000u128,
+        (0..1_000_000u128).prop_map(|x| x * ScaledUnsignedDecimal::<SCALE>::MULTIPLIER),
+        any::<u64>().prop_map(u128::from),
+        any::<u128>(),
+    ]
+}
+
+/// Any unsigned decimal.
+pub fn unsigned<const SCALE: u32>() -> impl Strategy<Value = ScaledUnsignedDecimal<SCALE>> {
+    raw_units::<SCALE>().prop_map(ScaledUnsignedDecimal::from_raw_value)
+}
+
+/// Any positive decimal, i.e. any unsigned decimal except 0.
+pub fn positive<const SCALE: u32>() -> impl Strategy<Value = ScaledPositiveDecimal<SCALE>> {
+    raw_units::<SCALE>().prop_map(|raw| {
+        ScaledPositiveDecimal::new(ScaledUnsignedDecimal::from_raw_value(raw.max(1)))
+            .expect("proptest: raw value is never 0")
+    })
+}
+
+/// Any signed decimal, never negative 0.
+pub fn signed<const SCALE: u32>() -> impl Strategy<Value = ScaledSignedDecimal<SCALE>> {
+    (unsigned::<SCALE>(), any::<bool>())
+        .prop_map(|(value, negative)| ScaledSignedDecimal::from_raw_value(value, negative))
+}
+
+/// Any ratio between 0 and 1.
+pub fn ratio() -> impl Strategy<Value = Ratio> {
+    let one = UnsignedDecimal::MULTIPLIER;
+    prop_oneof![Just(0), Just(one), 0..=one].prop_map(|raw| {
+        Ratio::new(UnsignedDecimal::from_raw_value(raw))
+            .expect("proptest: raw value is never above 1")
+    })
+}
+
+/// Denominators which are a small number times a large power of 2 or 10, so
+/// that two of them usually share a large common factor and their least
+/// common multiple may not fit in a `u128`.
+fn shared_factor_denom() -> impl Strategy<Value = u128> {
+    prop_oneof![
+        (1..16u128, 122..=124u32).prop_map(|(x, k)| x << k),
+        (1..16u128, 36..=37u32).prop_map(|(x, k)| x * 10u128.pow(k)),
+    ]
+}
+
+/// Any rational, with numerator and denominator biased like [unsigned].
+///
+/// Two thirds of the values instead have a small numerator over a
+/// denominator from `shared_factor_denom`, so that sums and differences often
+/// cancel part of the shared factor, and the exact result fits even though
+/// the least common denominator does not.
+pub fn rational() -> impl Strategy<Value = Rational> {
+    prop_oneof![
+        1 => (raw_units::<0>(), raw_units::<0>()).prop_map(|(numer, denom)| (numer, denom.max(1))),
+        2 => (0..16u128, shared_factor_denom()),
+    ]
+    .prop_map(|(numer, denom)| {
+        Rational::new(numer, denom).expect("proptest: denominator is never 0")
+    })
+}
+
+macro_rules! impl_arbitrary {
+    ($ty:ident, $strategy:ident) => {
+        impl<const SCALE: u32> Arbitrary for $ty<SCALE> {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with((): ()) -> Self::Strategy {
+                $strategy().boxed()
+            }
+        }
+    };
+    ($ty:ident, $strategy:expr) => {
+        impl Arbitrary for $ty {
+            type Parameters = ();
+            type Strategy = BoxedStrategy<Self>;
+
+            fn arbitrary_with((): ()) -> Self::Strategy {
+                $strategy.boxed()
+            }
+        }
+    };
+}
+
+impl_arbitrary!(ScaledUnsignedDecimal, unsigned);
+impl_arbitrary!(ScaledPositiveDecimal, positive);
+impl_arbitrary!(ScaledSignedDecimal, signed);
+impl_arbitrary!(Ratio, ratio());
+impl_arbitrary!(Percent, ratio().prop_map(Percent::from));
+impl_arbitrary!(BasisPoints, ratio().prop_map(BasisPoints::from));
+impl_arbitrary!(Rational, rational());
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::{prop_assert, prop_assert_eq, proptest};
+
+    use crate::{PositiveDecimal, SignedDecimal};
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_invariants(
+            p in any::<PositiveDecimal>(),
+            s in any::<SignedDecimal>(),
+            r in any::<Ratio>(),
+            q in any::<Rational>(),
+        ) {
+            prop_assert!(p.get_unsigned() > UnsignedDecimal::zero());
+            prop_assert_eq!(s.is_negative(), s < SignedDecimal::zero());
+            prop_assert!(r <= Ratio::ONE);
+            prop_assert_eq!(Rational::new(q.numer(), q.denom()), Ok(q));
+        }
+    }
+}
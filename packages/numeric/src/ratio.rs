@@ -0,0 +1,301 @@
+//! Ratios constrained to `[0, 1]`, such as fees, slippage tolerances and
+//! shares of a pool.
+//!
+//! [Ratio], [Percent] and [BasisPoints] all hold the same value, and convert
+//! into each other without loss. They differ in how they are written: a
+//! ratio of `0.003` is `0.3%` or `30bps`.
+
+use core::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+pub use private::Ratio;
+
+use crate::{Error, ParseMode, RoundingMode, UnsignedDecimal};
+
+mod private {
+    use crate::{Error, UnsignedDecimal};
+
+    /// A ratio between 0 and 1 inclusive, with the default scale.
+    #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+    pub struct Ratio {
+        // Invariant: never above 1
+        value: UnsignedDecimal,
+    }
+
+    impl Ratio {
+        /// A ratio of 0.
+        pub const ZERO: Self = Ratio {
+            value: UnsignedDecimal::from_raw_value(0),
+        };
+
+        /// A ratio of 1.
+        pub const ONE: Self = Ratio {
+            value: UnsignedDecimal::from_raw_value(UnsignedDecimal::MULTIPLIER),
+        };
+
+        /// Generate a new value, failing with [Error::Overflow] if it is
+        /// above 1.
+        pub fn new(value: UnsignedDecimal) -> Result<Self, Error> {
+            if value > Self::ONE.value {
+                Err(Error::Overflow)
+            } else {
+                Ok(Ratio { value })
+            }
+        }
+
+        /// Get the ratio as a decimal between 0 and 1.
+        pub fn get_unsigned(&self) -> UnsignedDecimal {
+            self.value
+        }
+    }
+}
+
+impl Ratio {
+    /// Parse a string using the given grammar, see [crate::parse].
+    pub fn parse_with(s: &str, mode: ParseMode) -> Result<Self, Error> {
+        UnsignedDecimal::parse_with(s, mode).and_then(Self::new)
+    }
+
+    /// The remaining part, i.e. `1 - self`.
+    pub fn complement(self) -> Self {
+        Self::ONE
+            .get_unsigned()
+            .checked_sub(self.get_unsigned())
+            .and_then(Self::new)
+            .expect("Ratio: complement is always in range")
+    }
+
+    /// Build a ratio from a value out of `denominator`, such as percent, if
+    /// it can be represented exactly.
+    fn from_parts(value: UnsignedDecimal, denominator: u128) -> Result<Self, Error> {
+        let raw = value.get_raw_value();
+        if raw % denominator != 0 {
+            return Err(Error::PrecisionLoss);
+        }
+        Self::new(UnsignedDecimal::from_raw_value(raw / denominator))
+    }
+
+    /// The ratio as a value out of `denominator`.
+    fn to_parts(self, denominator: u128) -> UnsignedDecimal {
+        // At most 10^6 * 10^4, which cannot overflow.
+        UnsignedDecimal::from_raw_value(self.get_unsigned().get_raw_value() * denominator)
+    }
+}
+
+/// Parses with [ParseMode::Lenient].
+impl FromStr for Ratio {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, ParseMode::Lenient)
+    }
+}
+
+/// Supports the same options as [UnsignedDecimal].
+impl Display for Ratio {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        Display::fmt(&self.get_unsigned(), f)
+    }
+}
+
+impl Debug for Ratio {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+/// Strip `unit` from the end of `s` and parse the rest as a decimal. Lenient
+/// mode also allows whitespace before the unit, e.g. `0.3 %`.
+fn parse_with_unit(s: &str, unit: &'static str, mode: ParseMode) -> Result<UnsignedDecimal, Error> {
+    let number = s
+        .strip_suffix(unit)
+        .ok_or(Error::MissingUnit { expected: unit })?;
+    let number = match mode {
+        ParseMode::Strict => number,
+        ParseMode::Lenient => number.trim_end(),
+    };
+    UnsignedDecimal::parse_with(number, mode)
+}
+
+macro_rules! ratio_unit {
+    ($(#[$meta:meta])* $ty:ident, $denominator:expr, $unit:expr, $max:expr) => {
+        $(#[$meta])*
+        #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default)]
+        pub struct $ty(Ratio);
+
+        impl $ty {
+            #[doc = concat!("Generate a new value between 0 and ", $max, ", failing with")]
+            /// [Error::Overflow] if it is too large and [Error::PrecisionLoss]
+            /// if it has too many fractional digits to be held as a [Ratio].
+            pub fn new(value: UnsignedDecimal) -> Result<Self, Error> {
+                Ratio::from_parts(value, $denominator).map($ty)
+            }
+
+            #[doc = concat!("Get the value, between 0 and ", $max, ".")]
+            pub fn get_value(&self) -> UnsignedDecimal {
+                self.0.to_parts($denominator)
+            }
+
+            #[doc = concat!("Parse a string such as `", $max, $unit, "`, see [crate::parse].")]
+            pub fn parse_with(s: &str, mode: ParseMode) -> Result<Self, Error> {
+                parse_with_unit(s, $unit, mode).and_then(Self::new)
+            }
+        }
+
+        impl From<Ratio> for $ty {
+            fn from(ratio: Ratio) -> Self {
+                $ty(ratio)
+            }
+        }
+
+        impl From<$ty> for Ratio {
+            fn from(value: $ty) -> Self {
+                value.0
+            }
+        }
+
+        /// Parses with [ParseMode::Lenient].
+        impl FromStr for $ty {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::parse_with(s, ParseMode::Lenient)
+            }
+        }
+
+        /// Formatter options apply to the number, before the unit.
+        impl Display for $ty {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                Display::fmt(&self.get_value(), f)?;
+                f.write_str($unit)
+            }
+        }
+
+        impl Debug for $ty {
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                write!(f, "{self}")
+            }
+        }
+
+        impl core::ops::Mul<$ty> for UnsignedDecimal {
+            type Output = Self;
+
+            fn mul(self, rhs: $ty) -> Self::Output {
+                self * rhs.0
+            }
+        }
+    };
+}
+
+ratio_unit!(
+    /// A [Ratio] written in percent, e.g. `0.3%`.
+    Percent,
+    100,
+    "%",
+    "100"
+);
+
+ratio_unit!(
+    /// A [Ratio] written in basis points, hundredths of a percent, e.g.
+    /// `30bps`.
+    BasisPoints,
+    10_000,
+    "bps",
+    "10000"
+);
+
+impl From<Percent> for BasisPoints {
+    fn from(value: Percent) -> Self {
+        BasisPoints(value.0)
+    }
+}
+
+impl From<BasisPoints> for Percent {
+    fn from(value: BasisPoints) -> Self {
+        Percent(value.0)
+    }
+}
+
+/// Truncates, see [UnsignedDecimal::mul_ratio].
+impl core::ops::Mul<Ratio> for UnsignedDecimal {
+    type Output = Self;
+
+    fn mul(self, rhs: Ratio) -> Self::Output {
+        self.mul_ratio(rhs, RoundingMode::Down)
+    }
+}
+
+impl UnsignedDecimal {
+    /// Take a share of this value. The result is never larger than `self`,
+    /// so this cannot overflow.
+    pub fn mul_ratio(self, ratio: Ratio, mode: RoundingMode) -> Self {
+        self.mul_rounded(ratio.get_unsigned(), mode)
+            .expect("UnsignedDecimal: multiplying by a ratio cannot overflow")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::dec;
+
+    use super::*;
+
+    #[test]
+    fn test_ratio() {
+        let r: Ratio = "0.003".parse().unwrap();
+        assert_eq!(r.to_string(), "0.003");
+        assert_eq!(r.complement().to_string(), "0.997");
+        assert_eq!(Ratio::new(dec!(1)), Ok(Ratio::ONE));
+        assert_eq!(Ratio::new(dec!(1.000001)), Err(Error::Overflow));
+        assert_eq!(
+            "-0.5".parse::<Ratio>(),
+            Err(Error::InvalidDigit { position: 0 })
+        );
+    }
+
+    #[test]
+    fn test_units() {
+        let p: Percent = "0.3%".parse().unwrap();
+        assert_eq!(p.get_value(), dec!(0.3));
+        assert_eq!(Ratio::from(p).to_string(), "0.003");
+        let bps = BasisPoints::from(p);
+        assert_eq!(bps.to_string(), "30bps");
+        assert_eq!(Percent::from(bps), p);
+        assert_eq!("30bps".parse::<BasisPoints>(), Ok(bps));
+        assert_eq!("0.3 %".parse::<Percent>(), Ok(p));
+        assert_eq!(format!("{:.2}", p), "0.30%");
+        assert_eq!(Percent::from(Ratio::ONE).to_string(), "100%");
+
+        assert_eq!(
+            Percent::parse_with("0.3 %", ParseMode::Strict),
+            Err(Error::InvalidDigit { position: 3 })
+        );
+        assert_eq!(
+            "0.3".parse::<Percent>(),
+            Err(Error::MissingUnit { expected: "%" })
+        );
+        assert_eq!("100.5%".parse::<Percent>(), Err(Error::Overflow));
+        assert_eq!("10001bps".parse::<BasisPoints>(), Err(Error::Overflow));
+        assert_eq!(
+            "0.5bps".parse::<BasisPoints>().unwrap().to_string(),
+            "0.5bps"
+        );
+        assert_eq!("0.00001%".parse::<Percent>(), Err(Error::PrecisionLoss));
+    }
+
+    #[test]
+    fn test_mul() {
+        let fee: BasisPoints = "30bps".parse().unwrap();
+        assert_eq!(dec!(1000) * fee, dec!(3));
+        assert_eq!(dec!(0.000333) * Ratio::from(fee), dec!(0));
+        assert_eq!(
+            dec!(0.000333).mul_ratio(fee.into(), RoundingMode::Up),
+            dec!(0.000001)
+        );
+        assert_eq!(UnsignedDecimal::MAX * Ratio::ONE, UnsignedDecimal::MAX);
+        let half: Percent = "50%".parse().unwrap();
+        assert_eq!(dec!(5) * half, dec!(2.5));
+    }
+}
@@ -0,0 +1,395 @@
+//! Exact non-negative fractions.
+
+use core::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+pub use private::Rational;
+
+use crate::{
+    parse::{Number, ParseMode},
+    wide::U256,
+    Error, RoundingMode, ScaledPositiveDecimal, ScaledUnsignedDecimal,
+};
+
+mod private {
+    use crate::Error;
+
+    /// A non-negative fraction of two `u128`s, always kept in lowest terms.
+    ///
+    /// Unlike the decimal types, division is exact. Operations fail with
+    /// [Error::Overflow] if the reduced numerator or denominator does not fit
+    /// in a `u128`.
+    #[derive(PartialEq, Eq, Clone, Copy)]
+    pub struct Rational {
+        // Invariant: denom is not 0, and gcd(numer, denom) is 1, so that
+        // equal values have equal representations.
+        numer: u128,
+        denom: u128,
+    }
+
+    impl Rational {
+        /// The value 0.
+        pub const ZERO: Self = Rational { numer: 0, denom: 1 };
+
+        /// The value 1.
+        pub const ONE: Self = Rational { numer: 1, denom: 1 };
+
+        /// Build `numer / denom`, reduced to lowest terms.
+        pub fn new(numer: u128, denom: u128) -> Result<Self, Error> {
+            if denom == 0 {
+                return Err(Error::DivisionByZero);
+            }
+            let gcd = super::gcd(numer, denom);
+            Ok(Rational {
+                numer: numer / gcd,
+                denom: denom / gcd,
+            })
+        }
+
+        pub fn numer(&self) -> u128 {
+            self.numer
+        }
+
+        pub fn denom(&self) -> u128 {
+            self.denom
+        }
+    }
+}
+
+/// Greatest common divisor, with `gcd(0, x) == x`.
+fn gcd(mut x: u128, mut y: u128) -> u128 {
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    x
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Rational {
+    pub fn is_zero(&self) -> bool {
+        self.numer() == 0
+    }
+
+    /// The reciprocal, `1 / self`. This is always exact.
+    pub fn recip(self) -> Result<Self, Error> {
+        Self::new(self.denom(), self.numer())
+    }
+
+    /// Convert to a decimal with the given rounding.
+    pub fn to_decimal<const SCALE: u32>(
+        self,
+        mode: RoundingMode,
+    ) -> Result<ScaledUnsignedDecimal<SCALE>, Error> {
+        self.round_to(SCALE, mode)
+    }
+
+    /// Convert to a decimal with at most `digits` fractional digits, with a
+    /// single rounding step.
+    pub fn round_to<const SCALE: u32>(
+        self,
+        digits: u32,
+        mode: RoundingMode,
+    ) -> Result<ScaledUnsignedDecimal<SCALE>, Error> {
+        let digits = digits.min(SCALE);
+        let units = U256::mul_u128(self.numer(), 10u128.pow(digits))
+            .div_rounded(self.denom(), false, mode)
+            .ok_or(Error::Overflow)?;
+        ScaledUnsignedDecimal::from_minor_units(units, digits)
+    }
+
+    /// Parse a fraction such as `11/10`, or a whole number such as `3`.
+    ///
+    /// Both parts are integers, following the [crate::parse] grammar
+    /// without a sign or fractional digits.
+    pub fn parse_with(s: &str, mode: ParseMode) -> Result<Self, Error> {
+        let (numer, denom) = match s.split_once('/') {
+            Some((numer, denom)) => {
+                let offset = numer.len() + 1;
+                let denom = parse_integer(denom, mode).map_err(|e| match e {
+                    Error::InvalidDigit { position } => Error::InvalidDigit {
+                        position: position + offset,
+                    },
+                    e => e,
+                })?;
+                (numer, denom)
+            }
+            None => (s, 1),
+        };
+        Self::new(parse_integer(numer, mode)?, denom)
+    }
+}
+
+fn parse_integer(s: &str, mode: ParseMode) -> Result<u128, Error> {
+    let number = Number::parse(s, mode)?;
+    if number.negative {
+        return Err(Error::InvalidDigit { position: 0 });
+    }
+    number.to_units(0, mode)
+}
+
+/// Checked arithmetic. Intermediate results are reduced or computed in 256
+/// bits, so these only fail with [Error::Overflow] if the reduced result does
+/// not fit.
+impl Rational {
+    pub fn checked_add(self, rhs: Self) -> Result<Self, Error> {
+        self.add_numerators(rhs, |x, y| x.checked_add(y).ok_or(Error::Overflow))
+    }
+
+    /// Fails with [Error::Underflow] if the result would be negative.
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, Error> {
+        // Check first, since finding the common denominator may overflow.
+        if self < rhs {
+            return Err(Error::Underflow);
+        }
+        self.add_numerators(rhs, |x, y| x.checked_sub(y).ok_or(Error::Underflow))
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, Error> {
+        // Cancel common factors first, so the result is already reduced.
+        let x = gcd(self.numer(), rhs.denom());
+        let y = gcd(rhs.numer(), self.denom());
+        let numer = (self.numer() / x).checked_mul(rhs.numer() / y);
+        let denom = (self.denom() / y).checked_mul(rhs.denom() / x);
+        match (numer, denom) {
+            (Some(numer), Some(denom)) => Self::new(numer, denom),
+            _ => Err(Error::Overflow),
+        }
+    }
+
+    pub fn checked_div(self, rhs: Self) -> Result<Self, Error> {
+        self.checked_mul(rhs.recip()?)
+    }
+
+    /// Combine both numerators over a common denominator with `op`, then
+    /// reduce.
+    ///
+    /// With `g = gcd(b, d)`, `a/b + c/d` is `t / (b/g * d)` where
+    /// `t = a * (d/g) + c * (b/g)`. Any common factor of `t` and the
+    /// denominator divides `g` (Knuth, TAOCP 4.5.1), so dividing both by
+    /// `gcd(t, g)` gives lowest terms. Only `t` can exceed a `u128`.
+    fn add_numerators(
+        self,
+        rhs: Self,
+        op: impl FnOnce(U256, U256) -> Result<U256, Error>,
+    ) -> Result<Self, Error> {
+        let g = gcd(self.denom(), rhs.denom());
+        let (x, y) = (self.denom() / g, rhs.denom() / g);
+        let t = op(
+            U256::mul_u128(self.numer(), y),
+            U256::mul_u128(rhs.numer(), x),
+        )?;
+        let g = gcd(g, t.div_rem_u128(g).1);
+        let numer = t.div_rem_u128(g).0.to_u128().ok_or(Error::Overflow)?;
+        let denom = x.checked_mul(rhs.denom() / g).ok_or(Error::Overflow)?;
+        Self::new(numer, denom)
+    }
+}
+
+impl<const SCALE: u32> From<ScaledUnsignedDecimal<SCALE>> for Rational {
+    fn from(value: ScaledUnsignedDecimal<SCALE>) -> Self {
+        Self::new(
+            value.get_raw_value(),
+            ScaledUnsignedDecimal::<SCALE>::MULTIPLIER,
+        )
+        .expect("Rational: the multiplier is never 0")
+    }
+}
+
+impl<const SCALE: u32> From<ScaledPositiveDecimal<SCALE>> for Rational {
+    fn from(value: ScaledPositiveDecimal<SCALE>) -> Self {
+        value.get_unsigned().into()
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        U256::mul_u128(self.numer(), other.denom())
+            .cmp(&U256::mul_u128(other.numer(), self.denom()))
+    }
+}
+
+/// Parses with [ParseMode::Lenient].
+impl FromStr for Rational {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, ParseMode::Lenient)
+    }
+}
+
+/// Renders as `numer/denom`, or just `numer` for whole numbers.
+impl Display for Rational {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if self.denom() == 1 {
+            write!(f, "{}", self.numer())
+        } else {
+            write!(f, "{}/{}", self.numer(), self.denom())
+        }
+    }
+}
+
+impl Debug for Rational {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl core::ops::Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("Rational: addition overflowed")
+    }
+}
+
+impl core::ops::Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs).expect("Rational: subtraction failed")
+    }
+}
+
+impl core::ops::Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("Rational: multiplication overflowed")
+    }
+}
+
+impl core::ops::Div for Rational {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs).expect("Rational: division failed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{dec, UnsignedDecimal};
+
+    use super::*;
+
+    fn r(s: &str) -> Rational {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_reduce() {
+        assert_eq!(Rational::new(10, 4).unwrap(), r("5/2"));
+        assert_eq!(r("0/7"), Rational::ZERO);
+        assert_eq!(r("6/3").to_string(), "2");
+        assert_eq!(Rational::from(dec!(1.5)), r("3/2"));
+        assert_eq!(Rational::new(1, 0), Err(Error::DivisionByZero));
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(r("1_000/3"), Rational::new(1000, 3).unwrap());
+        assert_eq!(
+            Rational::parse_with("1_000/3", ParseMode::Strict),
+            Err(Error::InvalidDigit { position: 1 })
+        );
+        assert_eq!(
+            "1/-3".parse::<Rational>(),
+            Err(Error::InvalidDigit { position: 2 })
+        );
+        assert_eq!(
+            "1.5/3".parse::<Rational>(),
+            Err(Error::TooManyFractionDigits { max: 0 })
+        );
+        assert_eq!("1/".parse::<Rational>(), Err(Error::Empty));
+        assert_eq!("1/0".parse::<Rational>(), Err(Error::DivisionByZero));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(r("1/3") + r("1/6"), r("1/2"));
+        assert_eq!(r("1/3") - r("1/6"), r("1/6"));
+        assert_eq!(r("2/3") * r("9/4"), r("3/2"));
+        assert_eq!(r("2/3") / r("4/9"), r("3/2"));
+        assert_eq!(r("1/3").checked_sub(r("1/2")), Err(Error::Underflow));
+        let tiny = Rational::new(1, u128::MAX).unwrap();
+        assert_eq!(
+            tiny.checked_sub(Rational::new(1, u128::MAX - 1).unwrap()),
+            Err(Error::Underflow)
+        );
+        assert_eq!(Rational::ZERO.recip(), Err(Error::DivisionByZero));
+        assert_eq!(r("7/3").recip().unwrap().recip().unwrap(), r("7/3"));
+
+        let max = Rational::new(u128::MAX, 1).unwrap();
+        assert_eq!(max.checked_add(Rational::ONE), Err(Error::Overflow));
+        // The least common denominator 35 * 2^125 does not fit, but the
+        // reduced sum does.
+        let x = Rational::new(1, 5 << 125).unwrap();
+        let y = Rational::new(5, 7 << 125).unwrap();
+        assert_eq!(x + y, Rational::new(1, 35 << 120).unwrap());
+        let z = Rational::new(6, 5 << 125).unwrap();
+        assert_eq!(z - Rational::new(2, 7 << 125).unwrap(), x + y);
+        // Reduction avoids overflow where possible.
+        let x = Rational::new(u128::MAX, 3).unwrap();
+        assert_eq!(x * r("3/5"), Rational::new(u128::MAX, 5).unwrap());
+    }
+
+    #[test]
+    fn test_ordering() {
+        assert!(r("1/3") < r("1/2"));
+        let big = Rational::new(u128::MAX, u128::MAX - 1).unwrap();
+        assert!(big > Rational::ONE);
+    }
+
+    #[test]
+    fn test_round_to() {
+        let x = r("1/8");
+        let round = |mode| x.round_to::<6>(2, mode).unwrap();
+        assert_eq!(round(RoundingMode::Down), dec!(0.12));
+        assert_eq!(round(RoundingMode::HalfEven), dec!(0.12));
+        assert_eq!(round(RoundingMode::HalfUp), dec!(0.13));
+        assert_eq!(x.round_to::<6>(9, RoundingMode::Up), Ok(dec!(0.125)));
+        assert_eq!(
+            Rational::new(u128::MAX, 1)
+                .unwrap()
+                .round_to::<6>(0, RoundingMode::Down),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_to_decimal() {
+        let third = r("1/3");
+        assert_eq!(
+            third.to_decimal::<6>(RoundingMode::Down),
+            Ok(dec!(0.333333))
+        );
+        assert_eq!(third.to_decimal::<6>(RoundingMode::Up), Ok(dec!(0.333334)));
+        assert_eq!(
+            Rational::new(u128::MAX, 1)
+                .unwrap()
+                .to_decimal::<6>(RoundingMode::Down),
+            Err(Error::Overflow)
+        );
+        let tiny = Rational::new(1, 10u128.pow(30)).unwrap();
+        assert_eq!(
+            tiny.to_decimal(RoundingMode::HalfEven),
+            Ok(UnsignedDecimal::zero())
+        );
+    }
+}
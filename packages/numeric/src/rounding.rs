@@ -0,0 +1,68 @@
+/// How to round a result which cannot be represented exactly.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum RoundingMode {
+    /// Round towards negative infinity.
+    Down,
+    /// Round towards positive infinity.
+    Up,
+    /// Round to the nearest value, with ties rounding away from zero.
+    HalfUp,
+    /// Round to the nearest value, with ties rounding to an even last digit.
+    HalfEven,
+    /// Round towards zero, i.e. truncate.
+    TowardZero,
+    /// Round away from zero.
+    AwayFromZero,
+}
+
+impl RoundingMode {
+    /// Decide whether the magnitude of a truncated quotient must be
+    /// incremented by one unit to apply this rounding mode.
+    ///
+    /// * `quotient_is_odd`: whether the truncated magnitude is odd
+    /// * `rem` and `divisor`: remainder of the truncating division
+    /// * `negative`: whether the exact result is negative
+    pub(crate) fn round_away(
+        self,
+        quotient_is_odd: bool,
+        rem: u128,
+        divisor: u128,
+        negative: bool,
+    ) -> bool {
+        if rem == 0 {
+            return false;
+        }
+        // Compare rem against divisor / 2 without overflowing.
+        let other = divisor - rem;
+        match self {
+            RoundingMode::Down => negative,
+            RoundingMode::Up => !negative,
+            RoundingMode::TowardZero => false,
+            RoundingMode::AwayFromZero => true,
+            RoundingMode::HalfUp => rem >= other,
+            RoundingMode::HalfEven => rem > other || (rem == other && quotient_is_odd),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_away() {
+        use RoundingMode::*;
+        // 2.5 and 3.5
+        for (q_odd, expected) in [(false, [0, 1, 1, 0, 0, 1]), (true, [0, 1, 1, 1, 0, 1])] {
+            let actual = [Down, Up, HalfUp, HalfEven, TowardZero, AwayFromZero]
+                .map(|mode| mode.round_away(q_odd, 5, 10, false) as i32);
+            assert_eq!(actual, expected);
+        }
+        // -2.4
+        let actual = [Down, Up, HalfUp, HalfEven, TowardZero, AwayFromZero]
+            .map(|mode| mode.round_away(false, 4, 10, true) as i32);
+        assert_eq!(actual, [1, 0, 0, 0, 0, 1]);
+        // Exact values never round
+        assert!(!AwayFromZero.round_away(true, 0, 10, false));
+    }
+}
@@ -0,0 +1,431 @@
+//! Serde support, enabled by the `serde` feature.
+//!
+//! By default, all decimal types serialize as their canonical string
+//! representation, e.g. `"1.5"`, which round trips without any loss of
+//! precision. Deserializing uses the [ParseMode::Strict] grammar and
+//! validates the value, so for example `"0"` is rejected for a
+//! [ScaledPositiveDecimal].
+//!
+//! [Ratio], [Percent], [BasisPoints] and [Rational] also serialize as
+//! strings, e.g. `"0.003"`, `"0.3%"`, `"30bps"` and `"11/10"`.
+//!
+//! Alternative representations are available with `#[serde(with = ...)]`:
+//!
+//! * [number]: a number, e.g. `1.5` in JSON, requires the `alloc` feature
+//! * [raw]: the raw integer number of smallest units, e.g. `1500000`
+//!
+//! ```ignore
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Config {
+//!     fee: numeric::UnsignedDecimal,
+//!     #[serde(with = "numeric::serde::number")]
+//!     threshold: numeric::PositiveDecimal,
+//!     #[serde(with = "numeric::serde::raw")]
+//!     balance: numeric::SignedDecimal,
+//! }
+//! ```
+
+use core::{
+    fmt::{Display, Formatter},
+    marker::PhantomData,
+};
+
+use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    BasisPoints, Error, ParseMode, Percent, Ratio, Rational, ScaledPositiveDecimal,
+    ScaledSignedDecimal, ScaledUnsignedDecimal,
+};
+
+use self::private::{Parse, Sealed};
+
+/// A decimal type supported by the [number] and [raw] modules.
+///
+/// This trait is sealed, and implemented for all decimal types in this crate.
+pub trait Decimal: Sealed + Display {}
+
+mod private {
+    use ::serde::{Deserializer, Serializer};
+
+    use crate::{Error, ParseMode};
+
+    /// Types which serialize as strings.
+    pub trait Parse: Sized {
+        /// Name used in error messages.
+        const EXPECTING: &'static str;
+
+        fn parse_with(s: &str, mode: ParseMode) -> Result<Self, Error>;
+    }
+
+    pub trait Sealed: Parse {
+        fn serialize_raw<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+
+        fn deserialize_raw<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error>;
+
+        fn from_raw_u128(raw: u128) -> Result<Self, Error>;
+
+        fn from_raw_i128(raw: i128) -> Result<Self, Error>;
+    }
+}
+
+impl<const SCALE: u32> Decimal for ScaledUnsignedDecimal<SCALE> {}
+impl<const SCALE: u32> Decimal for ScaledPositiveDecimal<SCALE> {}
+impl<const SCALE: u32> Decimal for ScaledSignedDecimal<SCALE> {}
+
+impl<const SCALE: u32> Sealed for ScaledUnsignedDecimal<SCALE> {
+    fn serialize_raw<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u128(self.get_raw_value())
+    }
+
+    fn deserialize_raw<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_u128(RawVisitor(PhantomData))
+    }
+
+    fn from_raw_u128(raw: u128) -> Result<Self, Error> {
+        Ok(Self::from_raw_value(raw))
+    }
+
+    fn from_raw_i128(raw: i128) -> Result<Self, Error> {
+        u128::try_from(raw)
+            .map(Self::from_raw_value)
+            .map_err(|_| Error::Underflow)
+    }
+}
+
+impl<const SCALE: u32> Sealed for ScaledPositiveDecimal<SCALE> {
+    fn serialize_raw<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get_unsigned().serialize_raw(serializer)
+    }
+
+    fn deserialize_raw<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_u128(RawVisitor(PhantomData))
+    }
+
+    fn from_raw_u128(raw: u128) -> Result<Self, Error> {
+        ScaledUnsignedDecimal::from_raw_u128(raw).and_then(Self::new)
+    }
+
+    fn from_raw_i128(raw: i128) -> Result<Self, Error> {
+        ScaledUnsignedDecimal::from_raw_i128(raw).and_then(Self::new)
+    }
+}
+
+impl<const SCALE: u32> Sealed for ScaledSignedDecimal<SCALE> {
+    /// Serialized as an `i128`, which cannot hold the most extreme values.
+    fn serialize_raw<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let magnitude = self.get_raw_value().get_raw_value();
+        let raw = if self.is_negative() {
+            0i128
+                .checked_sub_unsigned(magnitude)
+                .ok_or(Error::Underflow)
+        } else {
+            0i128.checked_add_unsigned(magnitude).ok_or(Error::Overflow)
+        };
+        serializer.serialize_i128(raw.map_err(::serde::ser::Error::custom)?)
+    }
+
+    fn deserialize_raw<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_i128(RawVisitor(PhantomData))
+    }
+
+    fn from_raw_u128(raw: u128) -> Result<Self, Error> {
+        Ok(Self::from_raw_value(
+            ScaledUnsignedDecimal::from_raw_value(raw),
+            false,
+        ))
+    }
+
+    fn from_raw_i128(raw: i128) -> Result<Self, Error> {
+        Ok(Self::from_raw_value(
+            ScaledUnsignedDecimal::from_raw_value(raw.unsigned_abs()),
+            raw < 0,
+        ))
+    }
+}
+
+macro_rules! impl_serde_str {
+    ($ty:ident, $expecting:expr) => {
+        impl<const SCALE: u32> Parse for $ty<SCALE> {
+            const EXPECTING: &'static str = $expecting;
+
+            fn parse_with(s: &str, mode: ParseMode) -> Result<Self, Error> {
+                Self::parse_with(s, mode)
+            }
+        }
+
+        impl<const SCALE: u32> Serialize for $ty<SCALE> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de, const SCALE: u32> Deserialize<'de> for $ty<SCALE> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(StrVisitor(PhantomData))
+            }
+        }
+    };
+}
+
+impl_serde_str!(ScaledUnsignedDecimal, "an unsigned decimal");
+impl_serde_str!(ScaledPositiveDecimal, "a positive decimal");
+impl_serde_str!(ScaledSignedDecimal, "a signed decimal");
+
+/// Like [impl_serde_str], for types without a scale parameter.
+macro_rules! impl_serde_ratio {
+    ($ty:ident, $expecting:expr) => {
+        impl Parse for $ty {
+            const EXPECTING: &'static str = $expecting;
+
+            fn parse_with(s: &str, mode: ParseMode) -> Result<Self, Error> {
+                Self::parse_with(s, mode)
+            }
+        }
+
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                deserializer.deserialize_str(StrVisitor(PhantomData))
+            }
+        }
+    };
+}
+
+impl_serde_ratio!(Ratio, "a ratio between 0 and 1");
+impl_serde_ratio!(Percent, "a percentage such as 0.3%");
+impl_serde_ratio!(BasisPoints, "basis points such as 30bps");
+impl_serde_ratio!(Rational, "a fraction such as 11/10");
+
+struct StrVisitor<T>(PhantomData<T>);
+
+impl<T: Parse> de::Visitor<'_> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{} string", T::EXPECTING)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        T::parse_with(v, ParseMode::Strict).map_err(E::custom)
+    }
+}
+
+/// Represent a decimal as a number, via `f64`.
+///
+/// Serializing is lossy for values with more than about 15 significant
+/// digits. Deserializing accepts integers, floats and strings, and fails if
+/// the value has more fractional digits than the type's scale.
+#[cfg(feature = "alloc")]
+pub mod number {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    pub fn serialize<T: Decimal, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        // Display is always a valid float literal.
+        let float: f64 = value
+            .to_string()
+            .parse()
+            .map_err(::serde::ser::Error::custom)?;
+        serializer.serialize_f64(float)
+    }
+
+    pub fn deserialize<'de, T: Decimal, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        deserializer.deserialize_any(NumberVisitor(PhantomData))
+    }
+
+    struct NumberVisitor<T>(PhantomData<T>);
+
+    impl<T: Decimal> de::Visitor<'_> for NumberVisitor<T> {
+        type Value = T;
+
+        fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+            write!(f, "{} number", T::EXPECTING)
+        }
+
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+            T::parse_with(&v.to_string(), ParseMode::Strict).map_err(E::custom)
+        }
+
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+            T::parse_with(&v.to_string(), ParseMode::Strict).map_err(E::custom)
+        }
+
+        fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+            if !v.is_finite() {
+                return Err(E::invalid_value(de::Unexpected::Float(v), &self));
+            }
+            // Display for f64 never uses exponent notation, and prints the
+            // shortest digits which round trip.
+            T::parse_with(&v.to_string(), ParseMode::Strict).map_err(E::custom)
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            T::parse_with(v, ParseMode::Strict).map_err(E::custom)
+        }
+    }
+}
+
+/// Represent a decimal as its raw integer number of smallest units.
+///
+/// For example, with the default scale of 6, `1.5` is represented as
+/// `1500000`. Unsigned types use a `u128` and signed types an `i128`, so
+/// signed values with a magnitude above `i128::MAX` fail to serialize.
+pub mod raw {
+    use super::*;
+
+    pub fn serialize<T: Decimal, S: Serializer>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        value.serialize_raw(serializer)
+    }
+
+    pub fn deserialize<'de, T: Decimal, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        T::deserialize_raw(deserializer)
+    }
+}
+
+struct RawVisitor<T>(PhantomData<T>);
+
+impl<T: Decimal> de::Visitor<'_> for RawVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "{} as an integer number of units", T::EXPECTING)
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        T::from_raw_u128(v.into()).map_err(E::custom)
+    }
+
+    fn visit_u128<E: de::Error>(self, v: u128) -> Result<Self::Value, E> {
+        T::from_raw_u128(v).map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        T::from_raw_i128(v.into()).map_err(E::custom)
+    }
+
+    fn visit_i128<E: de::Error>(self, v: i128) -> Result<Self::Value, E> {
+        T::from_raw_i128(v).map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{PositiveDecimal, SignedDecimal, UnsignedDecimal};
+
+    use super::*;
+
+    #[cfg(feature = "alloc")]
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Numbers {
+        #[serde(with = "number")]
+        unsigned: UnsignedDecimal,
+        #[serde(with = "number")]
+        signed: SignedDecimal,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Raw {
+        #[serde(with = "raw")]
+        positive: PositiveDecimal,
+        #[serde(with = "raw")]
+        signed: SignedDecimal,
+    }
+
+    #[test]
+    fn test_string() {
+        let x: UnsignedDecimal = "1.05".parse().unwrap();
+        assert_eq!(serde_json::to_string(&x).unwrap(), "\"1.05\"");
+        assert_eq!(
+            serde_json::from_str::<UnsignedDecimal>("\"1.05\"").unwrap(),
+            x
+        );
+        let x: SignedDecimal = "-1.05".parse().unwrap();
+        assert_eq!(serde_json::to_string(&x).unwrap(), "\"-1.05\"");
+        assert_eq!(
+            serde_json::from_str::<SignedDecimal>("\"-1.05\"").unwrap(),
+            x
+        );
+
+        serde_json::from_str::<PositiveDecimal>("\"0.000001\"").unwrap();
+        serde_json::from_str::<PositiveDecimal>("\"0\"").unwrap_err();
+        serde_json::from_str::<UnsignedDecimal>("\"-1\"").unwrap_err();
+        serde_json::from_str::<UnsignedDecimal>("\"0.0000001\"").unwrap_err();
+        serde_json::from_str::<UnsignedDecimal>("1").unwrap_err();
+        serde_json::from_str::<UnsignedDecimal>("\"1e3\"").unwrap_err();
+        serde_json::from_str::<UnsignedDecimal>("\".5\"").unwrap_err();
+    }
+
+    #[test]
+    fn test_ratios() {
+        let bps: BasisPoints = serde_json::from_str("\"30bps\"").unwrap();
+        assert_eq!(serde_json::to_string(&bps).unwrap(), "\"30bps\"");
+        let percent = Percent::from(bps);
+        assert_eq!(serde_json::to_string(&percent).unwrap(), "\"0.3%\"");
+        let ratio = Ratio::from(bps);
+        assert_eq!(serde_json::to_string(&ratio).unwrap(), "\"0.003\"");
+
+        serde_json::from_str::<Percent>("\"0.3\"").unwrap_err();
+        serde_json::from_str::<Percent>("\"0.3 %\"").unwrap_err();
+        serde_json::from_str::<Ratio>("\"1.5\"").unwrap_err();
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn test_number() {
+        let numbers = Numbers {
+            unsigned: "1.5".parse().unwrap(),
+            signed: "-0.25".parse().unwrap(),
+        };
+        let json = serde_json::to_string(&numbers).unwrap();
+        assert_eq!(json, r#"{"unsigned":1.5,"signed":-0.25}"#);
+        assert_eq!(serde_json::from_str::<Numbers>(&json).unwrap(), numbers);
+
+        let numbers: Numbers = serde_json::from_str(r#"{"unsigned":2,"signed":"-3"}"#).unwrap();
+        assert_eq!(numbers.unsigned, "2".parse().unwrap());
+        assert_eq!(numbers.signed, "-3".parse().unwrap());
+
+        serde_json::from_str::<Numbers>(r#"{"unsigned":-1,"signed":0}"#).unwrap_err();
+        serde_json::from_str::<Numbers>(r#"{"unsigned":0.1234567,"signed":0}"#).unwrap_err();
+    }
+
+    #[test]
+    fn test_raw() {
+        let raw = Raw {
+            positive: "1.5".parse().unwrap(),
+            signed: "-0.000002".parse().unwrap(),
+        };
+        let json = serde_json::to_string(&raw).unwrap();
+        assert_eq!(json, r#"{"positive":1500000,"signed":-2}"#);
+        assert_eq!(serde_json::from_str::<Raw>(&json).unwrap(), raw);
+
+        let json = r#"{"positive":340282366920938463463374607431768211455,"signed":0}"#;
+        let raw: Raw = serde_json::from_str(json).unwrap();
+        assert_eq!(raw.positive, PositiveDecimal::MAX);
+
+        serde_json::from_str::<Raw>(r#"{"positive":0,"signed":0}"#).unwrap_err();
+        serde_json::from_str::<Raw>(r#"{"positive":-1,"signed":0}"#).unwrap_err();
+        serde_json::from_str::<Raw>(r#"{"positive":"1","signed":0}"#).unwrap_err();
+
+        let raw = Raw {
+            positive: PositiveDecimal::MIN,
+            signed: SignedDecimal::MIN,
+        };
+        serde_json::to_string(&raw).unwrap_err();
+    }
+}
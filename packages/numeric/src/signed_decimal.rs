@@ -1,116 +1,460 @@
-use std::{
+use core::{
     fmt::{Debug, Display},
     str::FromStr,
 };
 
-pub use private::SignedDecimal;
+pub use private::ScaledSignedDecimal;
 
-use crate::UnsignedDecimal;
+use crate::{
+    format::{fmt_decimal, DecimalDisplay, FormatOptions},
+    parse::{Number, ParseMode},
+    wide::U256,
+    Error, RoundingMode, ScaledUnsignedDecimal, DEFAULT_SCALE,
+};
 
 mod private {
-    use crate::UnsignedDecimal;
+    use crate::ScaledUnsignedDecimal;
 
-    /// A signed version of [UnsignedDecimal]
-    #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-    pub struct SignedDecimal {
-        value: UnsignedDecimal,
+    /// A signed version of [ScaledUnsignedDecimal]
+    #[derive(PartialEq, Eq, Clone, Copy)]
+    pub struct ScaledSignedDecimal<const SCALE: u32> {
+        value: ScaledUnsignedDecimal<SCALE>,
         // Invariant: negative must be false whenever value is 0
         negative: bool,
     }
 
-    impl SignedDecimal {
-        pub(crate) fn from_raw_value(value: UnsignedDecimal, negative: bool) -> Self {
-            SignedDecimal {
+    impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+        /// The largest representable value.
+        pub const MAX: Self = ScaledSignedDecimal {
+            value: ScaledUnsignedDecimal::MAX,
+            negative: false,
+        };
+
+        /// The smallest representable value.
+        pub const MIN: Self = ScaledSignedDecimal {
+            value: ScaledUnsignedDecimal::MAX,
+            negative: true,
+        };
+
+        pub(crate) fn from_raw_value(value: ScaledUnsignedDecimal<SCALE>, negative: bool) -> Self {
+            ScaledSignedDecimal {
                 value,
                 negative: negative && value.get_raw_value() != 0,
             }
         }
-        pub(crate) fn get_raw_value(&self) -> UnsignedDecimal {
+        pub(crate) fn get_raw_value(&self) -> ScaledUnsignedDecimal<SCALE> {
             self.value
         }
-        pub(crate) fn is_negative(&self) -> bool {
+        /// Is this value strictly less than 0?
+        pub fn is_negative(&self) -> bool {
             self.negative
         }
     }
 }
 
-impl SignedDecimal {
-    pub fn negate(self) -> SignedDecimal {
-        SignedDecimal::from_raw_value(self.get_raw_value(), !self.is_negative())
+/// A signed decimal with the default 6 digits of precision.
+pub type SignedDecimal = ScaledSignedDecimal<DEFAULT_SCALE>;
+
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    pub fn zero() -> Self {
+        Self::from_raw_value(ScaledUnsignedDecimal::zero(), false)
+    }
+
+    pub fn one() -> Self {
+        Self::from_raw_value(ScaledUnsignedDecimal::one(), false)
+    }
+
+    pub fn negate(self) -> Self {
+        Self::from_raw_value(self.get_raw_value(), !self.is_negative())
+    }
+
+    /// Is this value strictly greater than 0?
+    pub fn is_positive(&self) -> bool {
+        !self.is_negative() && self.get_raw_value().get_raw_value() != 0
+    }
+
+    /// The absolute value.
+    pub fn abs(self) -> Self {
+        Self::from_raw_value(self.get_raw_value(), false)
+    }
+
+    /// The absolute value as an unsigned decimal.
+    pub fn unsigned_abs(self) -> ScaledUnsignedDecimal<SCALE> {
+        self.get_raw_value()
+    }
+
+    /// Returns -1, 0 or 1 depending on the sign of the value.
+    pub fn signum(self) -> Self {
+        let one = ScaledUnsignedDecimal::from_raw_value(ScaledUnsignedDecimal::<SCALE>::MULTIPLIER);
+        if self.is_negative() {
+            Self::from_raw_value(one, true)
+        } else if self.is_positive() {
+            Self::from_raw_value(one, false)
+        } else {
+            Self::zero()
+        }
     }
 }
 
-impl FromStr for SignedDecimal {
-    type Err = anyhow::Error;
+impl<const SCALE: u32> Default for ScaledSignedDecimal<SCALE> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.strip_prefix("-") {
-            Some(s) => {
-                let value: UnsignedDecimal = s.parse()?;
-                anyhow::ensure!(value.get_raw_value() != 0, "Cannot have a negative zero");
-                Ok(SignedDecimal::from_raw_value(value, true))
-            }
-            None => Ok(SignedDecimal::from_raw_value(s.parse()?, false)),
+impl<const SCALE: u32> From<ScaledUnsignedDecimal<SCALE>> for ScaledSignedDecimal<SCALE> {
+    fn from(value: ScaledUnsignedDecimal<SCALE>) -> Self {
+        Self::from_raw_value(value, false)
+    }
+}
+
+impl<const SCALE: u32> PartialOrd for ScaledSignedDecimal<SCALE> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const SCALE: u32> Ord for ScaledSignedDecimal<SCALE> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        match (self.is_negative(), other.is_negative()) {
+            (false, false) => self.get_raw_value().cmp(&other.get_raw_value()),
+            (true, true) => other.get_raw_value().cmp(&self.get_raw_value()),
+            (false, true) => core::cmp::Ordering::Greater,
+            (true, false) => core::cmp::Ordering::Less,
         }
     }
 }
 
-impl Display for SignedDecimal {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        if self.is_negative() {
-            write!(f, "-")?;
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    /// Parse a string using the given grammar, see [crate::parse].
+    pub fn parse_with(s: &str, mode: ParseMode) -> Result<Self, Error> {
+        let number = Number::parse(s, mode)?;
+        match number.to_units(SCALE, mode) {
+            Ok(0) if number.negative => Err(Error::NegativeZero),
+            Ok(units) => Ok(Self::from_raw_value(
+                ScaledUnsignedDecimal::from_raw_value(units),
+                number.negative,
+            )),
+            Err(Error::Overflow) if number.negative => Err(Error::Underflow),
+            Err(e) => Err(e),
         }
-        write!(f, "{}", self.get_raw_value())
     }
 }
 
-impl Debug for SignedDecimal {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+/// Parses with [ParseMode::Lenient].
+impl<const SCALE: u32> FromStr for ScaledSignedDecimal<SCALE> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, ParseMode::Lenient)
+    }
+}
+
+/// Supports the same options as [ScaledUnsignedDecimal].
+impl<const SCALE: u32> Display for ScaledSignedDecimal<SCALE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt_decimal(
+            f,
+            self.get_raw_value().get_raw_value(),
+            SCALE,
+            self.is_negative(),
+            FormatOptions::from_formatter(f),
+        )
+    }
+}
+
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    /// See [ScaledUnsignedDecimal::display_with].
+    pub fn display_with(self, options: FormatOptions) -> DecimalDisplay {
+        DecimalDisplay {
+            units: self.get_raw_value().get_raw_value(),
+            scale: SCALE,
+            negative: self.is_negative(),
+            options,
+        }
+    }
+}
+
+impl<const SCALE: u32> Debug for ScaledSignedDecimal<SCALE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{self}")
     }
 }
 
-impl std::ops::Add for SignedDecimal {
-    type Output = SignedDecimal;
+impl<const SCALE: u32> core::ops::Add for ScaledSignedDecimal<SCALE> {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        match (self.is_negative(), rhs.is_negative()) {
-            (false, false) => {
-                SignedDecimal::from_raw_value(self.get_raw_value() + rhs.get_raw_value(), false)
-            }
-            (true, true) => {
-                SignedDecimal::from_raw_value(self.get_raw_value() + rhs.get_raw_value(), true)
-            }
-            (false, true) => self - rhs.negate(),
-            (true, false) => rhs - self.negate(),
-        }
+        self.checked_add(rhs)
+            .expect("SignedDecimal: addition overflowed")
+    }
+}
+
+impl<const SCALE: u32> core::ops::Sub for ScaledSignedDecimal<SCALE> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("SignedDecimal: subtraction overflowed")
+    }
+}
+
+impl<const SCALE: u32> core::ops::AddAssign for ScaledSignedDecimal<SCALE> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const SCALE: u32> core::ops::SubAssign for ScaledSignedDecimal<SCALE> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const SCALE: u32> core::ops::Neg for ScaledSignedDecimal<SCALE> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.negate()
+    }
+}
+
+impl<const SCALE: u32> core::ops::Mul for ScaledSignedDecimal<SCALE> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("SignedDecimal: multiplication overflowed")
     }
 }
 
-impl std::ops::Sub for SignedDecimal {
-    type Output = SignedDecimal;
+impl<const SCALE: u32> core::ops::Div for ScaledSignedDecimal<SCALE> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs)
+            .expect("SignedDecimal: division failed")
+    }
+}
+
+impl<const SCALE: u32> core::ops::Add<ScaledUnsignedDecimal<SCALE>> for ScaledSignedDecimal<SCALE> {
+    type Output = Self;
+
+    fn add(self, rhs: ScaledUnsignedDecimal<SCALE>) -> Self::Output {
+        self + Self::from(rhs)
+    }
+}
+
+impl<const SCALE: u32> core::ops::Sub<ScaledUnsignedDecimal<SCALE>> for ScaledSignedDecimal<SCALE> {
+    type Output = Self;
+
+    fn sub(self, rhs: ScaledUnsignedDecimal<SCALE>) -> Self::Output {
+        self - Self::from(rhs)
+    }
+}
+
+impl<const SCALE: u32> core::ops::AddAssign<ScaledUnsignedDecimal<SCALE>>
+    for ScaledSignedDecimal<SCALE>
+{
+    fn add_assign(&mut self, rhs: ScaledUnsignedDecimal<SCALE>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const SCALE: u32> core::ops::SubAssign<ScaledUnsignedDecimal<SCALE>>
+    for ScaledSignedDecimal<SCALE>
+{
+    fn sub_assign(&mut self, rhs: ScaledUnsignedDecimal<SCALE>) {
+        *self = *self - rhs;
+    }
+}
+
+/// Subtracting two unsigned values gives a signed difference, which can
+/// never overflow.
+impl<const SCALE: u32> core::ops::Sub for ScaledUnsignedDecimal<SCALE> {
+    type Output = ScaledSignedDecimal<SCALE>;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        match (self.is_negative(), rhs.is_negative()) {
-            (false, false) => {
-                let x = self.get_raw_value();
-                let y = rhs.get_raw_value();
-                if x > y {
-                    SignedDecimal::from_raw_value(x.checked_sub(y).unwrap(), false)
-                } else {
-                    SignedDecimal::from_raw_value(y.checked_sub(x).unwrap(), true)
-                }
+        if self >= rhs {
+            ScaledSignedDecimal::from_raw_value(self.checked_sub(rhs).unwrap(), false)
+        } else {
+            ScaledSignedDecimal::from_raw_value(rhs.checked_sub(self).unwrap(), true)
+        }
+    }
+}
+
+/// Checked arithmetic, returning an [Error] instead of panicking.
+///
+/// Results above [ScaledSignedDecimal::MAX] fail with [Error::Overflow],
+/// results below [ScaledSignedDecimal::MIN] with [Error::Underflow].
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    pub fn checked_add(self, rhs: Self) -> Result<Self, Error> {
+        let x = self.get_raw_value();
+        let y = rhs.get_raw_value();
+        let negative = self.is_negative();
+        if negative == rhs.is_negative() {
+            match x.checked_add(y) {
+                Ok(value) => Ok(Self::from_raw_value(value, negative)),
+                Err(_) if negative => Err(Error::Underflow),
+                Err(e) => Err(e),
             }
-            (false, true) => self + rhs.negate(),
-            (true, false) => (self.negate() - rhs).negate(),
-            (true, true) => (self.negate() + rhs.negate()).negate(),
+        } else if x >= y {
+            Ok(Self::from_raw_value(x.checked_sub(y)?, negative))
+        } else {
+            Ok(Self::from_raw_value(y.checked_sub(x)?, !negative))
+        }
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, Error> {
+        self.checked_add(rhs.negate())
+    }
+
+    /// Truncates towards zero, see [ScaledSignedDecimal::mul_rounded].
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, Error> {
+        self.mul_rounded(rhs, RoundingMode::TowardZero)
+    }
+
+    /// Truncates towards zero, see [ScaledSignedDecimal::div_rounded].
+    pub fn checked_div(self, rhs: Self) -> Result<Self, Error> {
+        self.div_rounded(rhs, RoundingMode::TowardZero)
+    }
+}
+
+/// Multiplication and division with an explicit [RoundingMode].
+///
+/// [RoundingMode::Down] and [RoundingMode::Up] round towards negative and
+/// positive infinity respectively, so for negative results they differ from
+/// [RoundingMode::TowardZero] and [RoundingMode::AwayFromZero].
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    pub fn mul_rounded(self, rhs: Self, mode: RoundingMode) -> Result<Self, Error> {
+        let negative = self.is_negative() != rhs.is_negative();
+        let product = U256::mul_u128(
+            self.get_raw_value().get_raw_value(),
+            rhs.get_raw_value().get_raw_value(),
+        );
+        Self::from_rounded(
+            product.div_rounded(ScaledUnsignedDecimal::<SCALE>::MULTIPLIER, negative, mode),
+            negative,
+        )
+    }
+
+    pub fn div_rounded(self, rhs: Self, mode: RoundingMode) -> Result<Self, Error> {
+        let divisor = rhs.get_raw_value().get_raw_value();
+        if divisor == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        let negative = self.is_negative() != rhs.is_negative();
+        let numerator = U256::mul_u128(
+            self.get_raw_value().get_raw_value(),
+            ScaledUnsignedDecimal::<SCALE>::MULTIPLIER,
+        );
+        Self::from_rounded(numerator.div_rounded(divisor, negative, mode), negative)
+    }
+
+    fn from_rounded(magnitude: Option<u128>, negative: bool) -> Result<Self, Error> {
+        match magnitude {
+            Some(value) => Ok(Self::from_raw_value(
+                ScaledUnsignedDecimal::from_raw_value(value),
+                negative,
+            )),
+            None if negative => Err(Error::Underflow),
+            None => Err(Error::Overflow),
         }
     }
 }
 
+/// Saturating arithmetic, clamping the result to the range `MIN..=MAX`.
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or_else(saturate)
+    }
+
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).unwrap_or_else(saturate)
+    }
+
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs).unwrap_or_else(saturate)
+    }
+
+    /// Panics if `rhs` is 0.
+    pub fn saturating_div(self, rhs: Self) -> Self {
+        match self.overflowing_div(rhs) {
+            (value, false) => value,
+            (value, true) if value.is_negative() => Self::MIN,
+            (_, true) => Self::MAX,
+        }
+    }
+}
+
+fn saturate<const SCALE: u32>(e: Error) -> ScaledSignedDecimal<SCALE> {
+    match e {
+        Error::Underflow => ScaledSignedDecimal::MIN,
+        _ => ScaledSignedDecimal::MAX,
+    }
+}
+
+/// Overflowing arithmetic.
+///
+/// On overflow the magnitude wraps around to fit in the underlying 128-bit
+/// representation, while the sign is that of the true result.
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let negative = self.is_negative();
+        if negative == rhs.is_negative() {
+            let (value, overflowed) = self.get_raw_value().overflowing_add(rhs.get_raw_value());
+            (Self::from_raw_value(value, negative), overflowed)
+        } else {
+            // Values with opposite signs can never overflow.
+            (self.checked_add(rhs).unwrap(), false)
+        }
+    }
+
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        self.overflowing_add(rhs.negate())
+    }
+
+    /// Truncates towards zero, like [ScaledSignedDecimal::checked_mul].
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        let negative = self.is_negative() != rhs.is_negative();
+        let (value, overflowed) = self.get_raw_value().overflowing_mul(rhs.get_raw_value());
+        (Self::from_raw_value(value, negative), overflowed)
+    }
+
+    /// Truncates towards zero, like [ScaledSignedDecimal::checked_div].
+    /// Panics if `rhs` is 0.
+    pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+        let negative = self.is_negative() != rhs.is_negative();
+        let (value, overflowed) = self.get_raw_value().overflowing_div(rhs.get_raw_value());
+        (Self::from_raw_value(value, negative), overflowed)
+    }
+}
+
+/// Conversions between different scales, see [ScaledUnsignedDecimal::widen]
+/// and [ScaledUnsignedDecimal::narrow].
+impl<const SCALE: u32> ScaledSignedDecimal<SCALE> {
+    pub fn widen<const TO: u32>(self) -> Result<ScaledSignedDecimal<TO>, Error> {
+        match self.get_raw_value().widen() {
+            Ok(value) => Ok(ScaledSignedDecimal::from_raw_value(
+                value,
+                self.is_negative(),
+            )),
+            Err(_) if self.is_negative() => Err(Error::Underflow),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn narrow<const TO: u32>(self) -> Result<ScaledSignedDecimal<TO>, Error> {
+        self.get_raw_value()
+            .narrow()
+            .map(|value| ScaledSignedDecimal::from_raw_value(value, self.is_negative()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::UnsignedDecimal;
 
     #[test]
     fn test_basic_addition() {
@@ -121,4 +465,189 @@ mod tests {
         let z2: SignedDecimal = z.to_string().parse().unwrap();
         assert_eq!(z, z2);
     }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let p = |s| SignedDecimal::from_str(s).unwrap();
+        let max = SignedDecimal::MAX;
+        let min = SignedDecimal::MIN;
+        assert_eq!(max.checked_add(p("1")), Err(Error::Overflow));
+        assert_eq!(min.checked_add(p("-1")), Err(Error::Underflow));
+        assert_eq!(min.checked_sub(p("1")), Err(Error::Underflow));
+        assert_eq!(max.checked_add(min), Ok(p("0")));
+        assert_eq!(p("2").checked_sub(p("5")), Ok(p("-3")));
+        assert_eq!(p("-2").checked_sub(p("-2")), Ok(p("0")));
+    }
+
+    #[test]
+    fn test_saturating_arithmetic() {
+        let p = |s| SignedDecimal::from_str(s).unwrap();
+        let max = SignedDecimal::MAX;
+        let min = SignedDecimal::MIN;
+        assert_eq!(max.saturating_add(p("1")), max);
+        assert_eq!(min.saturating_sub(p("1")), min);
+        assert_eq!(p("1.5").saturating_sub(p("2")), p("-0.5"));
+        assert_eq!(max.saturating_mul(p("2")), max);
+        assert_eq!(max.saturating_mul(p("-2")), min);
+        assert_eq!(min.saturating_mul(p("-2")), max);
+        assert_eq!(p("-1.5").saturating_mul(p("2")), p("-3"));
+        assert_eq!(max.saturating_div(p("0.5")), max);
+        assert_eq!(max.saturating_div(p("-0.5")), min);
+        assert_eq!(p("-3").saturating_div(p("2")), p("-1.5"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_saturating_div_by_zero() {
+        SignedDecimal::one().saturating_div(SignedDecimal::zero());
+    }
+
+    #[test]
+    fn test_overflowing_arithmetic() {
+        let p = |s| SignedDecimal::from_str(s).unwrap();
+        let epsilon = p("0.000001");
+        assert_eq!(SignedDecimal::MIN.overflowing_sub(epsilon), (p("0"), true));
+        assert_eq!(SignedDecimal::MAX.overflowing_add(epsilon), (p("0"), true));
+        assert_eq!(p("-1").overflowing_add(p("3")), (p("2"), false));
+        assert_eq!(p("-1.5").overflowing_mul(p("2")), (p("-3"), false));
+        assert_eq!(p("-2").overflowing_div(p("-0.5")), (p("4"), false));
+        let (value, overflowed) = SignedDecimal::MIN.overflowing_mul(p("2"));
+        assert!(overflowed && value.is_negative());
+        assert_eq!(
+            value.unsigned_abs(),
+            UnsignedDecimal::MAX.overflowing_mul("2".parse().unwrap()).0
+        );
+        let (value, overflowed) = SignedDecimal::MAX.overflowing_div(p("-0.5"));
+        assert!(overflowed && value.is_negative());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = |s| SignedDecimal::from_str(s).unwrap_err();
+        assert_eq!(e("-0"), Error::NegativeZero);
+        assert_eq!(e("-0.000"), Error::NegativeZero);
+        assert_eq!(e("-"), Error::Empty);
+        assert_eq!(e("--1"), Error::InvalidDigit { position: 1 });
+        assert_eq!(e("-1.5a"), Error::InvalidDigit { position: 4 });
+        assert_eq!(e("-1e33"), Error::Underflow);
+        assert_eq!(e("+-1"), Error::InvalidDigit { position: 1 });
+        assert_eq!(
+            SignedDecimal::parse_with("+1", ParseMode::Strict),
+            Err(Error::InvalidDigit { position: 0 })
+        );
+        assert_eq!(
+            SignedDecimal::from_str("+1.5"),
+            SignedDecimal::from_str("1.5")
+        );
+    }
+
+    #[test]
+    fn test_rescale() {
+        let x: SignedDecimal = "-1.5".parse().unwrap();
+        let wide: ScaledSignedDecimal<9> = x.widen().unwrap();
+        assert_eq!(wide.to_string(), "-1.5");
+        assert_eq!(wide.narrow::<6>(), Ok(x));
+        assert_eq!(SignedDecimal::MIN.widen::<7>(), Err(Error::Underflow));
+    }
+
+    #[test]
+    fn test_rounded_arithmetic() {
+        use RoundingMode::*;
+        let p = |s| SignedDecimal::from_str(s).unwrap();
+        let modes = [Down, Up, HalfUp, HalfEven, TowardZero, AwayFromZero];
+
+        // -2 / 3 = -0.666666|666...
+        let results = modes.map(|mode| p("-2").div_rounded(p("3"), mode).unwrap().to_string());
+        assert_eq!(
+            results,
+            [
+                "-0.666667",
+                "-0.666666",
+                "-0.666667",
+                "-0.666667",
+                "-0.666666",
+                "-0.666667"
+            ]
+        );
+
+        // -0.000005 * -0.5 = 0.000002|5
+        let results = modes.map(|mode| {
+            p("-0.000005")
+                .mul_rounded(p("-0.5"), mode)
+                .unwrap()
+                .to_string()
+        });
+        assert_eq!(
+            results,
+            ["0.000002", "0.000003", "0.000003", "0.000002", "0.000002", "0.000003"]
+        );
+
+        // Rounding a tiny negative value towards zero must not give -0
+        assert_eq!(p("-0.000001").mul_rounded(p("0.1"), TowardZero), Ok(p("0")));
+        assert_eq!(p("1").div_rounded(p("0"), Up), Err(Error::DivisionByZero));
+        assert_eq!(
+            SignedDecimal::MIN.mul_rounded(p("2"), Down),
+            Err(Error::Underflow)
+        );
+    }
+
+    #[test]
+    fn test_ordering() {
+        let p = |s| SignedDecimal::from_str(s).unwrap();
+        assert!(p("-5") < p("3"));
+        assert!(p("-5") < p("-3"));
+        assert!(p("-0.1") < p("0"));
+        assert!(p("2.5") > p("2"));
+
+        let mut values = ["3", "-5", "0", "-1.5", "2.5", "-1"].map(p);
+        values.sort();
+        assert_eq!(values, ["-5", "-1.5", "-1", "0", "2.5", "3"].map(p));
+    }
+
+    #[test]
+    fn test_sign_helpers() {
+        let p = |s| SignedDecimal::from_str(s).unwrap();
+        assert_eq!(-p("2.5"), p("-2.5"));
+        assert_eq!(-p("0"), p("0"));
+        assert_eq!(p("-2.5").abs(), p("2.5"));
+        assert_eq!(p("-2.5").unsigned_abs(), "2.5".parse().unwrap());
+        assert_eq!(p("-2.5").signum(), p("-1"));
+        assert_eq!(p("0").signum(), p("0"));
+        assert_eq!(p("0.000001").signum(), p("1"));
+        assert!(p("0.1").is_positive());
+        assert!(!p("0").is_positive());
+        assert!(!p("0").is_negative());
+    }
+
+    #[test]
+    fn test_multiplication_and_division() {
+        let p = |s| SignedDecimal::from_str(s).unwrap();
+        assert_eq!(p("-2") * p("3.5"), p("-7"));
+        assert_eq!(p("-2") * p("-3.5"), p("7"));
+        assert_eq!(p("-7") / p("2"), p("-3.5"));
+        // Truncates towards zero
+        assert_eq!(p("-2") / p("3"), p("-0.666666"));
+        assert_eq!(p("1").checked_div(p("0")), Err(Error::DivisionByZero));
+        assert_eq!(
+            SignedDecimal::MAX.checked_mul(p("-2")),
+            Err(Error::Underflow)
+        );
+    }
+
+    #[test]
+    fn test_mixed_arithmetic() {
+        let p = |s| SignedDecimal::from_str(s).unwrap();
+        let u = |s| UnsignedDecimal::from_str(s).unwrap();
+        assert_eq!(p("-2") + u("3.5"), p("1.5"));
+        assert_eq!(p("2") - u("3.5"), p("-1.5"));
+        assert_eq!(u("2") - u("3.5"), p("-1.5"));
+        assert_eq!(u("3.5") - u("2"), p("1.5"));
+        assert_eq!(u("2") - u("2"), p("0"));
+
+        let mut pnl = SignedDecimal::zero();
+        pnl += u("10");
+        pnl -= u("12.5");
+        pnl += p("1");
+        assert_eq!(pnl, p("-1.5"));
+    }
 }
@@ -1,146 +1,429 @@
-use std::{
+use core::{
     fmt::{Debug, Display},
     str::FromStr,
 };
 
-use anyhow::Result;
-pub use private::UnsignedDecimal;
+pub use private::ScaledUnsignedDecimal;
+
+use crate::{
+    format::{fmt_decimal, DecimalDisplay, FormatOptions},
+    parse::{Number, ParseMode},
+    wide::U256,
+    Error, RoundingMode, DEFAULT_SCALE,
+};
 
 mod private {
-    /// Stored with 6 digits of precision
+    /// A non-negative decimal stored with `SCALE` digits of precision
     #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-    pub struct UnsignedDecimal {
+    pub struct ScaledUnsignedDecimal<const SCALE: u32> {
         value: u128,
     }
 
-    impl UnsignedDecimal {
-        pub(crate) fn from_raw_value(value: u128) -> Self {
-            UnsignedDecimal { value }
+    impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+        pub(crate) const fn from_raw_value(value: u128) -> Self {
+            ScaledUnsignedDecimal { value }
         }
-        pub(crate) fn get_raw_value(&self) -> u128 {
+        pub(crate) const fn get_raw_value(&self) -> u128 {
             self.value
         }
     }
 }
 
-const MULTIPLIER: u128 = 1_000_000;
+/// An unsigned decimal with the default 6 digits of precision.
+pub type UnsignedDecimal = ScaledUnsignedDecimal<DEFAULT_SCALE>;
+
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    /// The value of 1, i.e. `10^SCALE` raw units.
+    pub(crate) const MULTIPLIER: u128 = {
+        assert!(SCALE <= 38, "Decimal scale cannot exceed 38 digits");
+        10u128.pow(SCALE)
+    };
+
+    /// The largest representable value.
+    pub const MAX: Self = Self::from_raw_value(u128::MAX);
 
-impl UnsignedDecimal {
     pub fn zero() -> Self {
-        UnsignedDecimal::from_raw_value(0)
+        Self::from_raw_value(0)
+    }
+
+    pub fn one() -> Self {
+        Self::from_raw_value(Self::MULTIPLIER)
     }
 }
 
-impl Default for UnsignedDecimal {
+impl<const SCALE: u32> Default for ScaledUnsignedDecimal<SCALE> {
     fn default() -> Self {
         Self::zero()
     }
 }
 
-impl FromStr for UnsignedDecimal {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (whole, fraction) = match s.split_once('.') {
-            None => (s.parse()?, 0),
-            Some((whole, fraction)) => {
-                let whole: u128 = whole.parse()?;
-                let fraction = parse_fraction(fraction)?;
-                (whole, fraction)
-            }
-        };
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    /// Parse a string using the given grammar, see [crate::parse].
+    pub fn parse_with(s: &str, mode: ParseMode) -> Result<Self, Error> {
+        let number = Number::parse(s, mode)?;
+        if number.negative {
+            return Err(Error::InvalidDigit { position: 0 });
+        }
+        number.to_units(SCALE, mode).map(Self::from_raw_value)
+    }
+}
 
-        let value = whole * MULTIPLIER + fraction;
+/// Parses with [ParseMode::Lenient].
+impl<const SCALE: u32> FromStr for ScaledUnsignedDecimal<SCALE> {
+    type Err = Error;
 
-        Ok(UnsignedDecimal::from_raw_value(value))
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, ParseMode::Lenient)
     }
 }
 
-fn parse_fraction(s: &str) -> Result<u128> {
-    anyhow::ensure!(
-        s.len() <= 6,
-        "Unsigned decimal only supports up to 6 decimal points"
-    );
-    let mut x = s.parse()?;
-    for _ in s.len()..6 {
-        x *= 10;
+/// Supports precision (rounding half to even), width, fill, alignment, `+`
+/// to always print a sign, and `#` for thousands separators, e.g. `{:>#12.2}`
+/// renders `1234.5` as `    1,234.50`.
+impl<const SCALE: u32> Display for ScaledUnsignedDecimal<SCALE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        fmt_decimal(
+            f,
+            self.get_raw_value(),
+            SCALE,
+            false,
+            FormatOptions::from_formatter(f),
+        )
     }
-    Ok(x)
 }
 
-impl Display for UnsignedDecimal {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let value = self.get_raw_value();
-        let whole = value / MULTIPLIER;
-        let mut fraction = value % MULTIPLIER;
-        if fraction == 0 {
-            write!(f, "{whole}")
-        } else {
-            while fraction > 0 && fraction % 10 == 0 {
-                fraction /= 10;
-            }
-            write!(f, "{whole}.{fraction}")
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    /// Render with `options` rather than the formatter's precision and
+    /// flags, e.g. to pass them on from a wrapper's [Display] impl.
+    pub fn display_with(self, options: FormatOptions) -> DecimalDisplay {
+        DecimalDisplay {
+            units: self.get_raw_value(),
+            scale: SCALE,
+            negative: false,
+            options,
         }
     }
 }
 
-impl Debug for UnsignedDecimal {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl<const SCALE: u32> Debug for ScaledUnsignedDecimal<SCALE> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(f, "{self}")
     }
 }
 
-impl std::ops::Add for UnsignedDecimal {
-    type Output = UnsignedDecimal;
+impl<const SCALE: u32> core::ops::Add for ScaledUnsignedDecimal<SCALE> {
+    type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        UnsignedDecimal::from_raw_value(self.get_raw_value() + rhs.get_raw_value())
+        self.checked_add(rhs)
+            .expect("UnsignedDecimal: addition overflowed")
     }
 }
 
-impl std::ops::AddAssign for UnsignedDecimal {
+impl<const SCALE: u32> core::ops::AddAssign for ScaledUnsignedDecimal<SCALE> {
     fn add_assign(&mut self, rhs: Self) {
         *self = *self + rhs;
     }
 }
 
-impl UnsignedDecimal {
-    pub fn checked_sub(&self, rhs: Self) -> Result<UnsignedDecimal> {
-        let x = self.get_raw_value();
-        let y = rhs.get_raw_value();
-        if x >= y {
-            Ok(UnsignedDecimal::from_raw_value(x - y))
-        } else {
-            Err(anyhow::anyhow!(
-                "UnsignedDecimal: cannot subtract {x} - {y}"
-            ))
+impl<const SCALE: u32> core::ops::Mul for ScaledUnsignedDecimal<SCALE> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("UnsignedDecimal: multiplication overflowed")
+    }
+}
+
+impl<const SCALE: u32> core::ops::Div for ScaledUnsignedDecimal<SCALE> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.checked_div(rhs)
+            .expect("UnsignedDecimal: division failed")
+    }
+}
+
+/// Checked arithmetic, returning an [Error] instead of panicking.
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    pub fn checked_add(self, rhs: Self) -> Result<Self, Error> {
+        self.get_raw_value()
+            .checked_add(rhs.get_raw_value())
+            .map(Self::from_raw_value)
+            .ok_or(Error::Overflow)
+    }
+
+    pub fn checked_add_assign(&mut self, rhs: Self) -> Result<(), Error> {
+        *self = self.checked_add(rhs)?;
+        Ok(())
+    }
+
+    pub fn checked_sub(self, rhs: Self) -> Result<Self, Error> {
+        self.get_raw_value()
+            .checked_sub(rhs.get_raw_value())
+            .map(Self::from_raw_value)
+            .ok_or(Error::Underflow)
+    }
+
+    pub fn checked_sub_assign(&mut self, rhs: Self) -> Result<(), Error> {
+        *self = self.checked_sub(rhs)?;
+        Ok(())
+    }
+
+    pub fn checked_mul(self, rhs: Self) -> Result<Self, Error> {
+        match self.overflowing_mul(rhs) {
+            (value, false) => Ok(value),
+            (_, true) => Err(Error::Overflow),
         }
     }
 
-    pub fn checked_sub_assign(&mut self, rhs: UnsignedDecimal) -> Result<()> {
-        match self.checked_sub(rhs) {
-            Ok(value) => {
-                *self = value;
-                Ok(())
-            }
-            Err(e) => Err(e),
+    pub fn checked_div(self, rhs: Self) -> Result<Self, Error> {
+        if rhs.get_raw_value() == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        match self.overflowing_div(rhs) {
+            (value, false) => Ok(value),
+            (_, true) => Err(Error::Overflow),
         }
     }
 }
 
-impl std::ops::Mul for UnsignedDecimal {
-    type Output = UnsignedDecimal;
+/// Multiplication and division with an explicit [RoundingMode].
+///
+/// The plain operators and `checked_*` methods truncate, which is the same as
+/// [RoundingMode::Down] for unsigned values.
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    pub fn mul_rounded(self, rhs: Self, mode: RoundingMode) -> Result<Self, Error> {
+        U256::mul_u128(self.get_raw_value(), rhs.get_raw_value())
+            .div_rounded(Self::MULTIPLIER, false, mode)
+            .map(Self::from_raw_value)
+            .ok_or(Error::Overflow)
+    }
 
-    fn mul(self, rhs: Self) -> Self::Output {
-        UnsignedDecimal::from_raw_value(self.get_raw_value() * rhs.get_raw_value() / MULTIPLIER)
+    pub fn div_rounded(self, rhs: Self, mode: RoundingMode) -> Result<Self, Error> {
+        if rhs.get_raw_value() == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        U256::mul_u128(self.get_raw_value(), Self::MULTIPLIER)
+            .div_rounded(rhs.get_raw_value(), false, mode)
+            .map(Self::from_raw_value)
+            .ok_or(Error::Overflow)
+    }
+
+    /// Compute `self * mul / div` with a single rounding step.
+    ///
+    /// The product is held in a 256-bit intermediate, so unlike
+    /// `(self * mul) / div` this never loses precision on the multiplication,
+    /// and only fails if the final result does not fit.
+    pub fn mul_div(self, mul: Self, div: Self, mode: RoundingMode) -> Result<Self, Error> {
+        if div.get_raw_value() == 0 {
+            return Err(Error::DivisionByZero);
+        }
+        // With a multiplier M, self * mul / div is (x/M) * (y/M) / (z/M),
+        // which is (x * y / z) / M, so the raw values can be used directly.
+        U256::mul_u128(self.get_raw_value(), mul.get_raw_value())
+            .div_rounded(div.get_raw_value(), false, mode)
+            .map(Self::from_raw_value)
+            .ok_or(Error::Overflow)
+    }
+
+    /// Compute `self * rhs` rounded to at most `digits` fractional digits,
+    /// with a single rounding step.
+    ///
+    /// Calling [Self::round_to] on the result of [Self::mul_rounded] rounds
+    /// twice, which can give a different result for the half modes.
+    pub fn mul_rounded_to(self, rhs: Self, digits: u32, mode: RoundingMode) -> Result<Self, Error> {
+        if digits >= SCALE {
+            return self.mul_rounded(rhs, mode);
+        }
+        // Divide the exact product by unit * MULTIPLIER, which may not fit in
+        // a u128, in two steps. Rounding only compares the remainder against
+        // half the divisor, and MULTIPLIER is even, so doubling the second
+        // remainder and adding 1 for a non-zero first remainder gives the
+        // same comparison against 2 * MULTIPLIER.
+        let unit = 10u128.pow(SCALE - digits);
+        let (quotient, rem) =
+            U256::mul_u128(self.get_raw_value(), rhs.get_raw_value()).div_rem_u128(unit);
+        let (quotient, outer_rem) = quotient.div_rem_u128(Self::MULTIPLIER);
+        let quotient = quotient.to_u128().ok_or(Error::Overflow)?;
+        let rem = 2 * outer_rem + u128::from(rem != 0);
+        let quotient = if mode.round_away(quotient % 2 == 1, rem, 2 * Self::MULTIPLIER, false) {
+            quotient.checked_add(1).ok_or(Error::Overflow)?
+        } else {
+            quotient
+        };
+        quotient
+            .checked_mul(unit)
+            .map(Self::from_raw_value)
+            .ok_or(Error::Overflow)
     }
 }
 
-impl std::ops::Div for UnsignedDecimal {
-    type Output = UnsignedDecimal;
+/// Saturating arithmetic, clamping the result to the range `0..=MAX`.
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        self.checked_add(rhs).unwrap_or(Self::MAX)
+    }
 
-    fn div(self, rhs: Self) -> Self::Output {
-        UnsignedDecimal::from_raw_value(self.get_raw_value() * MULTIPLIER / rhs.get_raw_value())
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        self.checked_sub(rhs).unwrap_or_default()
+    }
+
+    pub fn saturating_mul(self, rhs: Self) -> Self {
+        self.checked_mul(rhs).unwrap_or(Self::MAX)
+    }
+
+    /// Panics if `rhs` is 0.
+    pub fn saturating_div(self, rhs: Self) -> Self {
+        match self.overflowing_div(rhs) {
+            (value, false) => value,
+            (_, true) => Self::MAX,
+        }
+    }
+}
+
+/// Overflowing arithmetic, returning the result wrapped around to fit in the
+/// underlying 128-bit representation along with whether wrapping occurred.
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+        let (value, overflowed) = self.get_raw_value().overflowing_add(rhs.get_raw_value());
+        (Self::from_raw_value(value), overflowed)
+    }
+
+    pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+        let (value, overflowed) = self.get_raw_value().overflowing_sub(rhs.get_raw_value());
+        (Self::from_raw_value(value), overflowed)
+    }
+
+    pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+        let (value, _) = U256::mul_u128(self.get_raw_value(), rhs.get_raw_value())
+            .div_rem_u128(Self::MULTIPLIER);
+        wrap(value)
+    }
+
+    /// Panics if `rhs` is 0.
+    pub fn overflowing_div(self, rhs: Self) -> (Self, bool) {
+        let (value, _) = U256::mul_u128(self.get_raw_value(), Self::MULTIPLIER)
+            .div_rem_u128(rhs.get_raw_value());
+        wrap(value)
+    }
+}
+
+fn wrap<const SCALE: u32>(value: U256) -> (ScaledUnsignedDecimal<SCALE>, bool) {
+    (
+        ScaledUnsignedDecimal::from_raw_value(value.low_u128()),
+        value.to_u128().is_none(),
+    )
+}
+
+/// Conversions between different scales.
+impl<const SCALE: u32> ScaledUnsignedDecimal<SCALE> {
+    /// Convert to a scale with at least as many digits of precision.
+    ///
+    /// This never loses precision, but may overflow since a larger scale
+    /// leaves less room for the whole part. Using a smaller target scale is
+    /// a compile-time error.
+    pub fn widen<const TO: u32>(self) -> Result<ScaledUnsignedDecimal<TO>, Error> {
+        const { assert!(TO >= SCALE, "widen cannot reduce the scale") };
+        self.get_raw_value()
+            .checked_mul(10u128.pow(TO - SCALE))
+            .map(ScaledUnsignedDecimal::from_raw_value)
+            .ok_or(Error::Overflow)
+    }
+
+    /// Convert to a scale with at most as many digits of precision.
+    ///
+    /// Fails with [Error::PrecisionLoss] if the value has more significant
+    /// fractional digits than the target scale allows. Using a larger target
+    /// scale is a compile-time error.
+    pub fn narrow<const TO: u32>(self) -> Result<ScaledUnsignedDecimal<TO>, Error> {
+        const { assert!(TO <= SCALE, "narrow cannot increase the scale") };
+        let divisor = 10u128.pow(SCALE - TO);
+        let value = self.get_raw_value();
+        if value % divisor == 0 {
+            Ok(ScaledUnsignedDecimal::from_raw_value(value / divisor))
+        } else {
+            Err(Error::PrecisionLoss)
+        }
+    }
+
+    /// Number of significant fractional digits, e.g. 2 for `1.25` and 0 for
+    /// `3`. This is the smallest scale the value can be narrowed to.
+    pub const fn fraction_digits(&self) -> u32 {
+        let mut value = self.get_raw_value();
+        let mut digits = SCALE;
+        while digits > 0 && value % 10 == 0 {
+            value /= 10;
+            digits -= 1;
+        }
+        digits
+    }
+
+    /// Round to at most `digits` fractional digits, failing with
+    /// [Error::Overflow] if rounding up does not fit.
+    pub fn round_to(self, digits: u32, mode: RoundingMode) -> Result<Self, Error> {
+        if digits >= SCALE {
+            return Ok(self);
+        }
+        let divisor = 10u128.pow(SCALE - digits);
+        let value = self.get_raw_value();
+        let (quotient, rem) = (value / divisor, value % divisor);
+        let quotient = if mode.round_away(quotient % 2 == 1, rem, divisor, false) {
+            quotient + 1
+        } else {
+            quotient
+        };
+        quotient
+            .checked_mul(divisor)
+            .map(Self::from_raw_value)
+            .ok_or(Error::Overflow)
+    }
+
+    /// The value as an integer number of minor units, each `10^-decimals`,
+    /// e.g. 150 for `1.5` with 2 decimals.
+    ///
+    /// Fails with [Error::PrecisionLoss] if the value has more than
+    /// `decimals` fractional digits, or [Error::Overflow] if `decimals` is
+    /// larger than the scale and the result does not fit.
+    pub fn to_minor_units(self, decimals: u32) -> Result<u128, Error> {
+        let value = self.get_raw_value();
+        if decimals <= SCALE {
+            let divisor = 10u128.pow(SCALE - decimals);
+            if value % divisor == 0 {
+                Ok(value / divisor)
+            } else {
+                Err(Error::PrecisionLoss)
+            }
+        } else if value == 0 {
+            // Even when 10^(decimals - SCALE) itself does not fit.
+            Ok(0)
+        } else {
+            10u128
+                .checked_pow(decimals - SCALE)
+                .and_then(|multiplier| value.checked_mul(multiplier))
+                .ok_or(Error::Overflow)
+        }
+    }
+
+    /// The inverse of [ScaledUnsignedDecimal::to_minor_units].
+    ///
+    /// Fails with [Error::Overflow] if the result does not fit, or
+    /// [Error::PrecisionLoss] if `decimals` is larger than the scale and the
+    /// units cannot be represented exactly.
+    pub fn from_minor_units(units: u128, decimals: u32) -> Result<Self, Error> {
+        if decimals <= SCALE {
+            units
+                .checked_mul(10u128.pow(SCALE - decimals))
+                .map(Self::from_raw_value)
+                .ok_or(Error::Overflow)
+        } else {
+            match 10u128.checked_pow(decimals - SCALE) {
+                Some(divisor) if units % divisor == 0 => Ok(Self::from_raw_value(units / divisor)),
+                // Anything non-zero is below the smallest unit.
+                None if units == 0 => Ok(Self::zero()),
+                _ => Err(Error::PrecisionLoss),
+            }
+        }
     }
 }
 
@@ -169,22 +452,41 @@ mod tests {
         assert_eq!(z, z2);
     }
 
-    #[test]
-    fn test_parse_fraction() {
-        assert_eq!(parse_fraction("0").unwrap(), 0);
-        assert_eq!(parse_fraction("123456").unwrap(), 123456);
-        parse_fraction("1234567").unwrap_err();
-        parse_fraction("12345678").unwrap_err();
-        assert_eq!(parse_fraction("12345").unwrap(), 123450);
-        parse_fraction("").unwrap_err();
-    }
-
     #[test]
     fn test_basic_parse() {
         UnsignedDecimal::from_str("5.6").unwrap();
         UnsignedDecimal::from_str("5.0").unwrap();
-        UnsignedDecimal::from_str("5.").unwrap_err();
         UnsignedDecimal::from_str("5").unwrap();
+        assert_eq!(
+            UnsignedDecimal::from_str("5."),
+            UnsignedDecimal::from_str("5")
+        );
+        assert_eq!(
+            UnsignedDecimal::from_str(".5"),
+            UnsignedDecimal::from_str("0.5")
+        );
+        assert_eq!(
+            UnsignedDecimal::from_str("+1_000.5e-1"),
+            UnsignedDecimal::from_str("100.05")
+        );
+        UnsignedDecimal::parse_with("5.", ParseMode::Strict).unwrap_err();
+        UnsignedDecimal::parse_with("+5", ParseMode::Strict).unwrap_err();
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let e = |s| UnsignedDecimal::from_str(s).unwrap_err();
+        assert_eq!(e(""), Error::Empty);
+        assert_eq!(e("."), Error::Empty);
+        assert_eq!(e("-5"), Error::InvalidDigit { position: 0 });
+        assert_eq!(e("12a.5"), Error::InvalidDigit { position: 2 });
+        assert_eq!(e("12.5x"), Error::InvalidDigit { position: 4 });
+        assert_eq!(e("1.2.3"), Error::InvalidDigit { position: 3 });
+        assert_eq!(e("0.1234567"), Error::TooManyFractionDigits { max: 6 });
+        assert_eq!(
+            e("99999999999999999999999999999999999999999"),
+            Error::Overflow
+        );
     }
 
     #[test]
@@ -196,6 +498,128 @@ mod tests {
         assert_eq!(x.to_string(), "0");
     }
 
+    #[test]
+    fn test_leading_fraction_zeros() {
+        for s in ["0.05", "1.000001", "3.0102"] {
+            let x: UnsignedDecimal = s.parse().unwrap();
+            assert_eq!(x.to_string(), s);
+        }
+    }
+
+    #[test]
+    fn test_parse_overflow() {
+        assert_eq!(
+            UnsignedDecimal::from_str("340282366920938463463374607431769"),
+            Err(Error::Overflow)
+        );
+        UnsignedDecimal::from_str(&UnsignedDecimal::MAX.to_string()).unwrap();
+    }
+
+    #[test]
+    fn test_other_scales() {
+        let x: ScaledUnsignedDecimal<8> = "0.00000001".parse().unwrap();
+        assert_eq!(x.to_string(), "0.00000001");
+        ScaledUnsignedDecimal::<8>::from_str("0.000000001").unwrap_err();
+
+        let x: ScaledUnsignedDecimal<0> = "42".parse().unwrap();
+        assert_eq!(x * x, "1764".parse().unwrap());
+        assert_eq!(x / "5".parse().unwrap(), "8".parse().unwrap());
+    }
+
+    #[test]
+    fn test_rescale() {
+        let x: UnsignedDecimal = "1.25".parse().unwrap();
+        let wide: ScaledUnsignedDecimal<8> = x.widen().unwrap();
+        assert_eq!(wide.to_string(), "1.25");
+        assert_eq!(wide.narrow::<6>(), Ok(x));
+        assert_eq!(wide.narrow::<2>().unwrap().to_string(), "1.25");
+        assert_eq!(wide.narrow::<1>(), Err(Error::PrecisionLoss));
+        assert_eq!(UnsignedDecimal::MAX.widen::<8>(), Err(Error::Overflow));
+        assert_eq!(x.fraction_digits(), 2);
+        assert_eq!(wide.fraction_digits(), 2);
+        assert_eq!(UnsignedDecimal::zero().fraction_digits(), 0);
+        assert_eq!(UnsignedDecimal::MAX.fraction_digits(), 6);
+    }
+
+    #[test]
+    fn test_round_to() {
+        let x: UnsignedDecimal = "1.255".parse().unwrap();
+        let round = |mode| x.round_to(2, mode).unwrap().to_string();
+        assert_eq!(round(RoundingMode::Down), "1.25");
+        assert_eq!(round(RoundingMode::Up), "1.26");
+        assert_eq!(round(RoundingMode::HalfEven), "1.26");
+        assert_eq!(x.round_to(6, RoundingMode::Up), Ok(x));
+        assert_eq!(
+            UnsignedDecimal::MAX.round_to(0, RoundingMode::Up),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_mul_rounded_to() {
+        let x: UnsignedDecimal = "0.5".parse().unwrap();
+        let y: UnsignedDecimal = "0.049999".parse().unwrap();
+        let round = |mode| x.mul_rounded_to(y, 2, mode).unwrap().to_string();
+        // The exact product is 0.0249995, which mul_rounded would round to
+        // 0.025 before round_to sees it.
+        assert_eq!(round(RoundingMode::Down), "0.02");
+        assert_eq!(round(RoundingMode::Up), "0.03");
+        assert_eq!(round(RoundingMode::HalfUp), "0.02");
+        assert_eq!(
+            x.mul_rounded(y, RoundingMode::HalfUp)
+                .and_then(|z| z.round_to(2, RoundingMode::HalfUp))
+                .unwrap()
+                .to_string(),
+            "0.03"
+        );
+        let x: UnsignedDecimal = "2.5".parse().unwrap();
+        let half: UnsignedDecimal = "0.02".parse().unwrap();
+        assert_eq!(
+            x.mul_rounded_to(half, 1, RoundingMode::HalfEven)
+                .unwrap()
+                .to_string(),
+            "0"
+        );
+        assert_eq!(
+            x.mul_rounded_to(half, 1, RoundingMode::HalfUp)
+                .unwrap()
+                .to_string(),
+            "0.1"
+        );
+        assert_eq!(
+            x.mul_rounded_to(y, 6, RoundingMode::Up),
+            x.mul_rounded(y, RoundingMode::Up)
+        );
+        assert_eq!(
+            UnsignedDecimal::MAX.mul_rounded_to(x, 2, RoundingMode::Down),
+            Err(Error::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_minor_units() {
+        let x: UnsignedDecimal = "1.5".parse().unwrap();
+        assert_eq!(x.to_minor_units(2), Ok(150));
+        assert_eq!(UnsignedDecimal::from_minor_units(150, 2), Ok(x));
+        assert_eq!(x.to_minor_units(0), Err(Error::PrecisionLoss));
+        assert_eq!(x.to_minor_units(8), Ok(150_000_000));
+        assert_eq!(UnsignedDecimal::from_minor_units(150_000_000, 8), Ok(x));
+        assert_eq!(
+            UnsignedDecimal::from_minor_units(1, 8),
+            Err(Error::PrecisionLoss)
+        );
+        assert_eq!(
+            UnsignedDecimal::from_minor_units(u128::MAX, 0),
+            Err(Error::Overflow)
+        );
+        assert_eq!(UnsignedDecimal::MAX.to_minor_units(7), Err(Error::Overflow));
+        assert_eq!(UnsignedDecimal::zero().to_minor_units(45), Ok(0));
+        assert_eq!(
+            UnsignedDecimal::from_minor_units(0, 45),
+            Ok(UnsignedDecimal::zero())
+        );
+    }
+
     #[test]
     fn test_debug() {
         for s in ["5", "5.2", "7.1", "0"] {
@@ -215,4 +639,136 @@ mod tests {
         let p = |s| UnsignedDecimal::from_str(s).unwrap();
         assert_eq!(p("2.5"), p("5") / p("2"));
     }
+
+    #[test]
+    fn test_rounded_arithmetic() {
+        use RoundingMode::*;
+        let p = |s| UnsignedDecimal::from_str(s).unwrap();
+        let modes = [Down, Up, HalfUp, HalfEven, TowardZero, AwayFromZero];
+
+        // 2 / 3 = 0.666666|666...
+        let results = modes.map(|mode| p("2").div_rounded(p("3"), mode).unwrap().to_string());
+        assert_eq!(
+            results,
+            ["0.666666", "0.666667", "0.666667", "0.666667", "0.666666", "0.666667"]
+        );
+
+        // 0.000005 * 0.5 = 0.000002|5, a tie with an even last digit
+        let results = modes.map(|mode| {
+            p("0.000005")
+                .mul_rounded(p("0.5"), mode)
+                .unwrap()
+                .to_string()
+        });
+        assert_eq!(
+            results,
+            ["0.000002", "0.000003", "0.000003", "0.000002", "0.000002", "0.000003"]
+        );
+
+        // 0.000007 * 0.5 = 0.000003|5, a tie with an odd last digit
+        assert_eq!(
+            p("0.000007").mul_rounded(p("0.5"), HalfEven),
+            Ok(p("0.000004"))
+        );
+
+        assert_eq!(p("1").div_rounded(p("0"), Up), Err(Error::DivisionByZero));
+        assert_eq!(
+            UnsignedDecimal::MAX.mul_rounded(p("1.000001"), Down),
+            Err(Error::Overflow)
+        );
+        assert_eq!(
+            UnsignedDecimal::MAX.mul_rounded(p("1"), Up),
+            Ok(UnsignedDecimal::MAX)
+        );
+    }
+
+    #[test]
+    fn test_mul_div_precision() {
+        let p = |s| UnsignedDecimal::from_str(s).unwrap();
+
+        // The intermediate product rounds to zero with two-step math.
+        let x = p("0.000001");
+        assert_eq!(x * x / x, p("0"));
+        assert_eq!(x.mul_div(x, x, RoundingMode::Down), Ok(x));
+
+        // Two truncating roundings lose precision compared to one:
+        // 1.000001 * 0.999999 = 0.999999|999999, then / 0.333333 gives 3,
+        // while the exact result is 3.000003|000003.
+        let a = p("1.000001");
+        let b = p("0.999999");
+        let c = p("0.333333");
+        assert_eq!((a * b) / c, p("3"));
+        assert_eq!(a.mul_div(b, c, RoundingMode::Down), Ok(p("3.000003")));
+
+        // Constant product swap on a large pool, where K itself does not fit.
+        let usd = p("100000000000000000");
+        let euro = p("100000000000000000");
+        let new_usd = p("100000000000000000.5");
+        assert_eq!(usd.checked_mul(euro), Err(Error::Overflow));
+        assert_eq!(
+            usd.mul_div(euro, new_usd, RoundingMode::Up),
+            Ok(p("99999999999999999.500001"))
+        );
+    }
+
+    #[test]
+    fn test_mul_div_large_values() {
+        let p = |s| UnsignedDecimal::from_str(s).unwrap();
+        let big = p("100000000000000000000");
+        assert_eq!(big.checked_mul(big), Err(Error::Overflow));
+        assert_eq!(big.mul_div(big, big, RoundingMode::Down), Ok(big));
+        assert_eq!(
+            big.mul_div(big, p("1"), RoundingMode::Down),
+            Err(Error::Overflow)
+        );
+        assert_eq!(
+            big.mul_div(big, p("0"), RoundingMode::Down),
+            Err(Error::DivisionByZero)
+        );
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let p = |s| UnsignedDecimal::from_str(s).unwrap();
+        let max = UnsignedDecimal::MAX;
+        assert_eq!(max.checked_add(p("0.000001")), Err(Error::Overflow));
+        assert_eq!(p("1").checked_sub(p("1.5")), Err(Error::Underflow));
+        assert_eq!(max.checked_mul(p("2")), Err(Error::Overflow));
+        assert_eq!(max.checked_mul(p("1")), Ok(max));
+        assert_eq!(p("5").checked_div(p("0")), Err(Error::DivisionByZero));
+        assert_eq!(max.checked_div(p("0.5")), Err(Error::Overflow));
+        assert_eq!(p("7.5").checked_div(p("2.5")), Ok(p("3")));
+    }
+
+    #[test]
+    fn test_large_multiplication() {
+        // The product of the raw values does not fit in a u128, but the
+        // result does.
+        let p = |s| UnsignedDecimal::from_str(s).unwrap();
+        let x = p("10000000000000000");
+        assert_eq!(x * x, p("100000000000000000000000000000000"));
+    }
+
+    #[test]
+    fn test_saturating_arithmetic() {
+        let p = |s| UnsignedDecimal::from_str(s).unwrap();
+        let max = UnsignedDecimal::MAX;
+        assert_eq!(max.saturating_add(p("1")), max);
+        assert_eq!(p("1").saturating_sub(p("2")), p("0"));
+        assert_eq!(max.saturating_mul(p("1.5")), max);
+        assert_eq!(max.saturating_div(p("0.1")), max);
+        assert_eq!(p("5").saturating_div(p("2")), p("2.5"));
+    }
+
+    #[test]
+    fn test_overflowing_arithmetic() {
+        let p = |s| UnsignedDecimal::from_str(s).unwrap();
+        let max = UnsignedDecimal::MAX;
+        let epsilon = p("0.000001");
+        assert_eq!(max.overflowing_add(epsilon), (p("0"), true));
+        assert_eq!(p("0").overflowing_sub(epsilon), (max, true));
+        assert_eq!(p("2").overflowing_mul(p("3")), (p("6"), false));
+        assert!(max.overflowing_mul(p("2")).1);
+        assert_eq!(p("6").overflowing_div(p("3")), (p("2"), false));
+    }
 }
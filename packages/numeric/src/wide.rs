@@ -0,0 +1,301 @@
+//! Minimal 256-bit unsigned integer, used for intermediate results which may
+//! not fit in a `u128`.
+
+use crate::RoundingMode;
+
+/// A 256-bit unsigned integer, stored as two 128-bit halves.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub(crate) struct U256 {
+    hi: u128,
+    lo: u128,
+}
+
+const LOW_MASK: u128 = u64::MAX as u128;
+
+impl U256 {
+    pub(crate) fn from_u128(lo: u128) -> Self {
+        U256 { hi: 0, lo }
+    }
+
+    /// Full product of two `u128`s, which can never overflow.
+    pub(crate) fn mul_u128(x: u128, y: u128) -> Self {
+        let (x1, x0) = (x >> 64, x & LOW_MASK);
+        let (y1, y0) = (y >> 64, y & LOW_MASK);
+
+        let p00 = x0 * y0;
+        let p01 = x0 * y1;
+        let p10 = x1 * y0;
+        let p11 = x1 * y1;
+
+        // At most 3 * (2^64 - 1), so this cannot overflow.
+        let mid = (p00 >> 64) + (p01 & LOW_MASK) + (p10 & LOW_MASK);
+
+        U256 {
+            hi: p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64),
+            lo: (p00 & LOW_MASK) | (mid << 64),
+        }
+    }
+
+    /// Multiply by a `u128`, returning [None] on overflow.
+    pub(crate) fn checked_mul_u128(self, y: u128) -> Option<Self> {
+        let low = U256::mul_u128(self.lo, y);
+        let high = U256::mul_u128(self.hi, y).to_u128()?;
+        Some(U256 {
+            hi: low.hi.checked_add(high)?,
+            lo: low.lo,
+        })
+    }
+
+    /// Add a `u128`, returning [None] on overflow.
+    pub(crate) fn checked_add_u128(self, y: u128) -> Option<Self> {
+        let (lo, carry) = self.lo.overflowing_add(y);
+        Some(U256 {
+            hi: self.hi.checked_add(u128::from(carry))?,
+            lo,
+        })
+    }
+
+    /// Add another [U256], returning [None] on overflow.
+    pub(crate) fn checked_add(self, y: Self) -> Option<Self> {
+        let (lo, carry) = self.lo.overflowing_add(y.lo);
+        Some(U256 {
+            hi: self.hi.checked_add(y.hi)?.checked_add(u128::from(carry))?,
+            lo,
+        })
+    }
+
+    /// Subtract another [U256], returning [None] if the result is negative.
+    pub(crate) fn checked_sub(self, y: Self) -> Option<Self> {
+        let (lo, borrow) = self.lo.overflowing_sub(y.lo);
+        Some(U256 {
+            hi: self.hi.checked_sub(y.hi)?.checked_sub(u128::from(borrow))?,
+            lo,
+        })
+    }
+
+    /// Shift left by `shift` bits, returning [None] if any set bits are lost.
+    pub(crate) fn checked_shl(self, shift: u32) -> Option<Self> {
+        if shift == 0 {
+            Some(self)
+        } else if shift >= 256 || self.bit_len() + shift > 256 {
+            if self == U256::from_u128(0) {
+                Some(self)
+            } else {
+                None
+            }
+        } else if shift >= 128 {
+            Some(U256 {
+                hi: self.lo << (shift - 128),
+                lo: 0,
+            })
+        } else {
+            Some(U256 {
+                hi: (self.hi << shift) | (self.lo >> (128 - shift)),
+                lo: self.lo << shift,
+            })
+        }
+    }
+
+    /// Shift right by `shift` bits, discarding the bits shifted out.
+    pub(crate) fn shr(self, shift: u32) -> Self {
+        if shift == 0 {
+            self
+        } else if shift >= 256 {
+            U256::from_u128(0)
+        } else if shift >= 128 {
+            U256::from_u128(self.hi >> (shift - 128))
+        } else {
+            U256 {
+                hi: self.hi >> shift,
+                lo: (self.lo >> shift) | (self.hi << (128 - shift)),
+            }
+        }
+    }
+
+    /// Number of significant bits, i.e. 0 for the value 0.
+    pub(crate) fn bit_len(self) -> u32 {
+        if self.hi != 0 {
+            256 - self.hi.leading_zeros()
+        } else {
+            128 - self.lo.leading_zeros()
+        }
+    }
+
+    /// Integer square root, rounded down.
+    pub(crate) fn isqrt(self) -> u128 {
+        if self == U256::from_u128(0) {
+            return 0;
+        }
+        // Newton's method starting from a power of two which is at least the
+        // true root, so that every step decreases monotonically.
+        let shift = self.bit_len().div_ceil(2);
+        let mut root = if shift >= 128 { u128::MAX } else { 1 << shift };
+        loop {
+            let (quotient, _) = self.div_rem_u128(root);
+            // While root >= sqrt(self), the quotient is at most root.
+            let quotient = quotient.to_u128().unwrap_or(u128::MAX);
+            let next = (root >> 1) + (quotient >> 1) + (root & quotient & 1);
+            if next >= root {
+                return root;
+            }
+            root = next;
+        }
+    }
+
+    /// Divide by a `u128`, returning the quotient and remainder.
+    ///
+    /// Panics if `divisor` is 0.
+    pub(crate) fn div_rem_u128(self, divisor: u128) -> (Self, u128) {
+        assert!(divisor != 0, "U256: division by zero");
+        if self.hi == 0 {
+            return (U256::from_u128(self.lo / divisor), self.lo % divisor);
+        }
+
+        // Schoolbook binary long division. The remainder is always less than
+        // the divisor, so it fits in a u128 apart from the single bit shifted
+        // out on each step, which we track in carry.
+        let mut quotient = U256 { hi: 0, lo: 0 };
+        let mut rem: u128 = 0;
+        for i in (0..256).rev() {
+            let carry = rem >> 127;
+            rem = (rem << 1) | self.bit(i);
+            if carry == 1 || rem >= divisor {
+                rem = rem.wrapping_sub(divisor);
+                quotient.set_bit(i);
+            }
+        }
+        (quotient, rem)
+    }
+
+    /// Divide by a `u128`, rounding the quotient according to `mode`.
+    ///
+    /// `negative` indicates whether the exact result is negative, which
+    /// affects the direction of [RoundingMode::Down] and [RoundingMode::Up].
+    /// Returns [None] if the rounded magnitude does not fit in a `u128`.
+    ///
+    /// Panics if `divisor` is 0.
+    pub(crate) fn div_rounded(
+        self,
+        divisor: u128,
+        negative: bool,
+        mode: RoundingMode,
+    ) -> Option<u128> {
+        let (quotient, rem) = self.div_rem_u128(divisor);
+        let quotient = quotient.to_u128()?;
+        if mode.round_away(quotient % 2 == 1, rem, divisor, negative) {
+            quotient.checked_add(1)
+        } else {
+            Some(quotient)
+        }
+    }
+
+    /// Returns the value if it fits in a `u128`.
+    pub(crate) fn to_u128(self) -> Option<u128> {
+        if self.hi == 0 {
+            Some(self.lo)
+        } else {
+            None
+        }
+    }
+
+    /// The lower 128 bits, i.e. the value wrapped around to fit in a `u128`.
+    pub(crate) fn low_u128(self) -> u128 {
+        self.lo
+    }
+
+    fn bit(&self, i: u32) -> u128 {
+        if i >= 128 {
+            (self.hi >> (i - 128)) & 1
+        } else {
+            (self.lo >> i) & 1
+        }
+    }
+
+    fn set_bit(&mut self, i: u32) {
+        if i >= 128 {
+            self.hi |= 1 << (i - 128);
+        } else {
+            self.lo |= 1 << i;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_small_values() {
+        let x = U256::mul_u128(123_456, 789_012);
+        assert_eq!(x.to_u128(), Some(123_456 * 789_012));
+        let (q, r) = x.div_rem_u128(1_000);
+        assert_eq!(q.to_u128(), Some(123_456 * 789_012 / 1_000));
+        assert_eq!(r, 123_456 * 789_012 % 1_000);
+    }
+
+    #[test]
+    fn test_shifts() {
+        let x = U256::from_u128(0b1011);
+        assert_eq!(x.checked_shl(200).unwrap().shr(200), x);
+        assert_eq!(x.checked_shl(253), None);
+        assert_eq!(x.checked_shl(252).unwrap().bit_len(), 256);
+        assert_eq!(
+            x.checked_shl(130).unwrap().shr(129).to_u128(),
+            Some(0b10110)
+        );
+        assert_eq!(x.shr(2).to_u128(), Some(0b10));
+        assert_eq!(x.shr(300).to_u128(), Some(0));
+    }
+
+    #[test]
+    fn test_checked_mul_add() {
+        let x = U256::mul_u128(u128::MAX, 3);
+        assert_eq!(x.checked_mul_u128(5), Some(U256::mul_u128(u128::MAX, 15)));
+        assert_eq!(x.checked_mul_u128(u128::MAX), None);
+        let y = U256::from_u128(u128::MAX).checked_add_u128(1).unwrap();
+        assert_eq!(y.to_u128(), None);
+        assert_eq!(y.shr(128).to_u128(), Some(1));
+
+        let max = U256::mul_u128(u128::MAX, u128::MAX);
+        assert_eq!(x.checked_add(x), Some(U256::mul_u128(u128::MAX, 6)));
+        assert_eq!(max.checked_add(max), None);
+        assert_eq!(
+            y.checked_sub(U256::from_u128(1)),
+            Some(U256::from_u128(u128::MAX))
+        );
+        assert_eq!(U256::from_u128(1).checked_sub(y), None);
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for x in [0u128, 1, 2, 3, 4, 15, 16, 17, 1 << 100, u128::MAX] {
+            let root = U256::from_u128(x).isqrt();
+            assert!(U256::mul_u128(root, root) <= U256::from_u128(x));
+            assert!(U256::mul_u128(root + 1, root + 1) > U256::from_u128(x));
+        }
+        assert_eq!(U256::mul_u128(u128::MAX, u128::MAX).isqrt(), u128::MAX);
+        assert_eq!(
+            U256::mul_u128(u128::MAX, u128::MAX - 1).isqrt(),
+            u128::MAX - 1
+        );
+    }
+
+    #[test]
+    fn test_round_trip_large_values() {
+        let x = U256::mul_u128(u128::MAX, u128::MAX);
+        assert_eq!(x.to_u128(), None);
+        let (q, r) = x.div_rem_u128(u128::MAX);
+        assert_eq!(q.to_u128(), Some(u128::MAX));
+        assert_eq!(r, 0);
+
+        let x = U256::mul_u128(u128::MAX - 5, 1 << 100);
+        let (q, r) = x.div_rem_u128(1 << 100);
+        assert_eq!(q.to_u128(), Some(u128::MAX - 5));
+        assert_eq!(r, 0);
+
+        let (q, r) = x.div_rem_u128(7);
+        assert_eq!(q.to_u128(), None);
+        // (2^128 - 6) * 2^100 mod 7
+        assert_eq!(r, 3);
+    }
+}
@@ -0,0 +1,621 @@
+//! Check the arithmetic of every type against an exact big-rational
+//! reference implementation, using the strategies from the `proptest`
+//! feature.
+//!
+//! `checked_pow`, `checked_powi`, `exp`, `ln` and `to_f64_lossy` are
+//! approximations, so they are checked against their documented error bounds
+//! instead.
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{Signed, Zero};
+use numeric::{
+    proptest::{ratio, rational, signed, unsigned},
+    BasisPoints, Error, ParseMode, Percent, PositiveDecimal, Rational, RoundingMode,
+    ScaledUnsignedDecimal, SignedDecimal, UnsignedDecimal, DEFAULT_SCALE,
+};
+use proptest::prelude::*;
+
+const MODES: [RoundingMode; 6] = [
+    RoundingMode::Down,
+    RoundingMode::Up,
+    RoundingMode::HalfUp,
+    RoundingMode::HalfEven,
+    RoundingMode::TowardZero,
+    RoundingMode::AwayFromZero,
+];
+
+fn multiplier() -> BigInt {
+    BigInt::from(10u32).pow(DEFAULT_SCALE)
+}
+
+fn from_unsigned(x: UnsignedDecimal) -> BigRational {
+    let raw = u128::from_le_bytes(x.to_bytes());
+    BigRational::new(raw.into(), multiplier())
+}
+
+fn from_signed(x: SignedDecimal) -> BigRational {
+    let magnitude = from_unsigned(x.unsigned_abs());
+    if x.is_negative() {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+fn from_scaled<const SCALE: u32>(x: ScaledUnsignedDecimal<SCALE>) -> BigRational {
+    BigRational::new(x.to_raw_units().into(), BigInt::from(10u32).pow(SCALE))
+}
+
+fn from_rational(x: Rational) -> BigRational {
+    BigRational::new(x.numer().into(), x.denom().into())
+}
+
+fn from_raw(raw: u128) -> UnsignedDecimal {
+    UnsignedDecimal::from_bytes(raw.to_le_bytes())
+}
+
+fn raw(x: UnsignedDecimal) -> BigInt {
+    u128::from_le_bytes(x.to_bytes()).into()
+}
+
+/// `10^-digits`.
+fn tolerance(digits: u32) -> BigRational {
+    BigRational::new(1.into(), BigInt::from(10u32).pow(digits))
+}
+
+/// Half a unit in the last place, the error of rounding to nearest.
+fn half_unit() -> BigRational {
+    BigRational::new(1.into(), multiplier() * 2u32)
+}
+
+/// `e^x` with a relative error of about `10^-99`, using its Taylor series
+/// with 100 digits of fixed-point precision. Far more accurate than the
+/// `10^-33` bounds being checked.
+fn reference_exp(x: &BigRational) -> BigRational {
+    if x.is_negative() {
+        return reference_exp(&-x).recip();
+    }
+    let one = BigInt::from(10u32).pow(100);
+    let x = (x * &one).to_integer();
+    let mut sum = one.clone();
+    let mut term = one.clone();
+    let mut n = 1u32;
+    loop {
+        term = &term * &x / &one / n;
+        if term.is_zero() {
+            break BigRational::new(sum, one);
+        }
+        sum += &term;
+        n += 1;
+    }
+}
+
+/// Round to an integer, following the documented [RoundingMode] semantics.
+fn round(x: &BigRational, mode: RoundingMode) -> BigInt {
+    let floor = x.floor();
+    let frac = x - &floor;
+    let floor = floor.to_integer();
+    if frac.is_zero() {
+        return floor;
+    }
+    let half = BigRational::new(1.into(), 2.into());
+    let round_up = match mode {
+        RoundingMode::Down => false,
+        RoundingMode::Up => true,
+        RoundingMode::TowardZero => x.is_negative(),
+        RoundingMode::AwayFromZero => !x.is_negative(),
+        RoundingMode::HalfUp if frac == half => !x.is_negative(),
+        RoundingMode::HalfEven if frac == half => !(&floor % 2u32).is_zero(),
+        RoundingMode::HalfUp | RoundingMode::HalfEven => frac > half,
+    };
+    if round_up {
+        floor + 1
+    } else {
+        floor
+    }
+}
+
+/// The expected unsigned result for an exact value.
+fn to_unsigned(x: &BigRational, mode: RoundingMode) -> Result<UnsignedDecimal, Error> {
+    let raw = round(&(x * multiplier()), mode);
+    if raw.is_negative() {
+        return Err(Error::Underflow);
+    }
+    let raw = u128::try_from(raw).map_err(|_| Error::Overflow)?;
+    Ok(UnsignedDecimal::from_bytes(raw.to_le_bytes()))
+}
+
+/// The expected unsigned result for an exact value, rounded to `digits`
+/// fractional digits.
+fn to_unsigned_digits(
+    x: &BigRational,
+    digits: u32,
+    mode: RoundingMode,
+) -> Result<UnsignedDecimal, Error> {
+    let unit = BigInt::from(10u32).pow(digits);
+    let rounded = BigRational::new(round(&(x * &unit), mode), unit);
+    to_unsigned(&rounded, RoundingMode::Down)
+}
+
+/// The expected signed result for an exact value.
+fn to_signed(x: &BigRational, mode: RoundingMode) -> Result<SignedDecimal, Error> {
+    let raw = round(&(x * multiplier()), mode);
+    let magnitude = u128::try_from(raw.abs()).map_err(|_| {
+        if raw.is_negative() {
+            Error::Underflow
+        } else {
+            Error::Overflow
+        }
+    })?;
+    let mut bytes = [0; 17];
+    bytes[..16].copy_from_slice(&magnitude.to_le_bytes());
+    bytes[16] = u8::from(raw.is_negative());
+    SignedDecimal::from_bytes(bytes)
+}
+
+/// Saturate an out of range result the way the unsigned types do.
+fn saturate_unsigned(res: Result<UnsignedDecimal, Error>) -> UnsignedDecimal {
+    match res {
+        Ok(value) => value,
+        Err(Error::Overflow) => UnsignedDecimal::MAX,
+        Err(_) => UnsignedDecimal::zero(),
+    }
+}
+
+/// The expected result of an `overflowing_*` operation from the exact number
+/// of raw units: those units wrapped to 128 bits, and whether they did not
+/// fit.
+fn wrap(raw: &BigInt) -> (u128, bool) {
+    let modulus = BigInt::from(1u8) << 128;
+    let wrapped = ((raw % &modulus) + &modulus) % &modulus;
+    (
+        u128::try_from(wrapped).unwrap(),
+        u128::try_from(raw).is_err(),
+    )
+}
+
+/// The exact number of raw units of `x`, truncated towards zero.
+fn truncated_raw(x: &BigRational) -> BigInt {
+    (x * multiplier()).trunc().to_integer()
+}
+
+/// The expected rational result for an exact value.
+fn to_rational(x: &BigRational) -> Result<Rational, Error> {
+    if x.is_negative() {
+        return Err(Error::Underflow);
+    }
+    match (u128::try_from(x.numer()), u128::try_from(x.denom())) {
+        (Ok(numer), Ok(denom)) => Rational::new(numer, denom),
+        _ => Err(Error::Overflow),
+    }
+}
+
+proptest! {
+    #[test]
+    fn unsigned_add_sub(x in unsigned(), y in unsigned()) {
+        let (bx, by) = (from_unsigned(x), from_unsigned(y));
+        prop_assert_eq!(x.checked_add(y), to_unsigned(&(&bx + &by), RoundingMode::Down));
+        prop_assert_eq!(x.checked_sub(y), to_unsigned(&(&bx - &by), RoundingMode::Down));
+        prop_assert_eq!(Ok(x - y), to_signed(&(&bx - &by), RoundingMode::Down));
+    }
+
+    #[test]
+    fn unsigned_mul_div(x in unsigned(), y in unsigned(), z in unsigned()) {
+        let (bx, by, bz) = (from_unsigned(x), from_unsigned(y), from_unsigned(z));
+        prop_assert_eq!(x.checked_mul(y), to_unsigned(&(&bx * &by), RoundingMode::Down));
+        if by.is_zero() {
+            prop_assert_eq!(x.checked_div(y), Err(Error::DivisionByZero));
+        } else {
+            prop_assert_eq!(x.checked_div(y), to_unsigned(&(&bx / &by), RoundingMode::Down));
+        }
+        for mode in MODES {
+            prop_assert_eq!(x.mul_rounded(y, mode), to_unsigned(&(&bx * &by), mode));
+            if by.is_zero() {
+                prop_assert_eq!(x.div_rounded(y, mode), Err(Error::DivisionByZero));
+            } else {
+                prop_assert_eq!(x.div_rounded(y, mode), to_unsigned(&(&bx / &by), mode));
+            }
+            if !bz.is_zero() {
+                prop_assert_eq!(x.mul_div(y, z, mode), to_unsigned(&(&bx * &by / &bz), mode));
+            }
+            for digits in 0..=DEFAULT_SCALE {
+                prop_assert_eq!(
+                    x.mul_rounded_to(y, digits, mode),
+                    to_unsigned_digits(&(&bx * &by), digits, mode)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn positive_arithmetic(x in any::<PositiveDecimal>(), y in any::<PositiveDecimal>()) {
+        let (bx, by) = (from_unsigned(x.get_unsigned()), from_unsigned(y.get_unsigned()));
+        let truncated = |exact: &BigRational| {
+            to_unsigned(exact, RoundingMode::Down).and_then(PositiveDecimal::new)
+        };
+        prop_assert_eq!(x.checked_add(y), truncated(&(&bx + &by)));
+        prop_assert_eq!(x.checked_sub(y), truncated(&(&bx - &by)));
+        prop_assert_eq!(x.checked_mul(y), truncated(&(&bx * &by)));
+        prop_assert_eq!(x.checked_div(y), truncated(&(&bx / &by)));
+        for mode in MODES {
+            let expected = to_unsigned(&(&bx * &by), mode).and_then(PositiveDecimal::new);
+            prop_assert_eq!(x.mul_rounded(y, mode), expected);
+            let expected = to_unsigned(&(&bx / &by), mode).and_then(PositiveDecimal::new);
+            prop_assert_eq!(x.div_rounded(y, mode), expected);
+        }
+    }
+
+    #[test]
+    fn signed_arithmetic(x in signed(), y in signed()) {
+        let (bx, by) = (from_signed(x), from_signed(y));
+        prop_assert_eq!(x.checked_add(y), to_signed(&(&bx + &by), RoundingMode::Down));
+        prop_assert_eq!(x.checked_sub(y), to_signed(&(&bx - &by), RoundingMode::Down));
+        prop_assert_eq!(x.checked_mul(y), to_signed(&(&bx * &by), RoundingMode::TowardZero));
+        if by.is_zero() {
+            prop_assert_eq!(x.checked_div(y), Err(Error::DivisionByZero));
+        } else {
+            prop_assert_eq!(x.checked_div(y), to_signed(&(&bx / &by), RoundingMode::TowardZero));
+        }
+        for mode in MODES {
+            prop_assert_eq!(x.mul_rounded(y, mode), to_signed(&(&bx * &by), mode));
+            if by.is_zero() {
+                prop_assert_eq!(x.div_rounded(y, mode), Err(Error::DivisionByZero));
+            } else {
+                prop_assert_eq!(x.div_rounded(y, mode), to_signed(&(&bx / &by), mode));
+            }
+        }
+    }
+
+    #[test]
+    fn rational_arithmetic(x in rational(), y in rational()) {
+        let (bx, by) = (from_rational(x), from_rational(y));
+        prop_assert_eq!(x.checked_add(y), to_rational(&(&bx + &by)));
+        prop_assert_eq!(x.checked_sub(y), to_rational(&(&bx - &by)));
+        prop_assert_eq!(x.checked_mul(y), to_rational(&(&bx * &by)));
+        if by.is_zero() {
+            prop_assert_eq!(x.checked_div(y), Err(Error::DivisionByZero));
+        } else {
+            prop_assert_eq!(x.checked_div(y), to_rational(&(&bx / &by)));
+        }
+        prop_assert_eq!(x.cmp(&y), bx.cmp(&by));
+        for mode in MODES {
+            prop_assert_eq!(x.to_decimal(mode), to_unsigned(&bx, mode));
+            for digits in 0..=DEFAULT_SCALE {
+                prop_assert_eq!(x.round_to(digits, mode), to_unsigned_digits(&bx, digits, mode));
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt(x in unsigned()) {
+        // sqrt(raw / M) * M = sqrt(raw * M)
+        let n = raw(x) * multiplier();
+        let floor = n.sqrt();
+        // floor(sqrt(n) + 1/2) = floor((floor(2 * sqrt(n)) + 1) / 2)
+        let nearest = ((&n * 4u32).sqrt() + 1u32) / 2u32;
+        for mode in MODES {
+            let expected = match mode {
+                RoundingMode::Down | RoundingMode::TowardZero => floor.clone(),
+                RoundingMode::Up | RoundingMode::AwayFromZero if &floor * &floor == n => {
+                    floor.clone()
+                }
+                RoundingMode::Up | RoundingMode::AwayFromZero => &floor + 1u32,
+                RoundingMode::HalfUp | RoundingMode::HalfEven => nearest.clone(),
+            };
+            prop_assert_eq!(raw(x.sqrt(mode)), expected);
+            if let Ok(p) = PositiveDecimal::new(x) {
+                prop_assert_eq!(p.sqrt(mode).get_unsigned(), x.sqrt(mode));
+            }
+        }
+    }
+
+    #[test]
+    fn checked_pow(x in unsigned(), exp in 0..=64u32) {
+        let exact = from_unsigned(x).pow(exp as i32);
+        // A relative error below exp * 10^-34, then rounded to nearest.
+        let bound = &exact * tolerance(34) * BigInt::from(exp) + half_unit();
+        match x.checked_pow(exp) {
+            Ok(y) => prop_assert!((from_unsigned(y) - &exact).abs() <= bound),
+            Err(e) => {
+                prop_assert_eq!(e, Error::Overflow);
+                prop_assert!(&exact + &bound > from_unsigned(UnsignedDecimal::MAX));
+            }
+        }
+    }
+
+    #[test]
+    fn checked_powi(x in signed(), exp in -64..=64i32) {
+        let bx = from_signed(x);
+        if bx.is_zero() && exp < 0 {
+            prop_assert_eq!(x.checked_powi(exp), Err(Error::DivisionByZero));
+            return Ok(());
+        }
+        let exact = bx.pow(exp);
+        // A relative error below |exp| * 10^-34 for the power, and below
+        // (|exp| + 1) * 10^-34 for its reciprocal, then rounded to nearest.
+        let digits = exp.unsigned_abs() + u32::from(exp < 0);
+        let bound = exact.abs() * tolerance(34) * BigInt::from(digits) + half_unit();
+        let max = from_unsigned(UnsignedDecimal::MAX);
+        match x.checked_powi(exp) {
+            Ok(y) => prop_assert!((from_signed(y) - &exact).abs() <= bound),
+            Err(e) => {
+                let expected = if exact.is_negative() { Error::Underflow } else { Error::Overflow };
+                prop_assert_eq!(e, expected);
+                prop_assert!(exact.abs() + &bound > max);
+            }
+        }
+    }
+
+    #[test]
+    fn exp(x in prop_oneof![unsigned(), (0..80_000_000u128).prop_map(from_raw)]) {
+        let bx = from_unsigned(x);
+        // e^75 does not fit, so there is no need to approximate larger
+        // inputs.
+        if bx > BigRational::from_integer(80.into()) {
+            prop_assert_eq!(x.exp(), Err(Error::Overflow));
+            return Ok(());
+        }
+        let exact = reference_exp(&bx);
+        // A relative error below 10^-33, then rounded to nearest.
+        let bound = &exact * tolerance(33) + half_unit();
+        match x.exp() {
+            Ok(y) => prop_assert!((from_unsigned(y) - &exact).abs() <= bound),
+            Err(e) => {
+                prop_assert_eq!(e, Error::Overflow);
+                prop_assert!(&exact + &bound > from_unsigned(UnsignedDecimal::MAX));
+            }
+        }
+    }
+
+    #[test]
+    fn ln(x in any::<PositiveDecimal>()) {
+        // An absolute error below 10^-33, then rounded to nearest, so the
+        // exact logarithm is within bound of y. Since exp is increasing, x
+        // is then between e^(y - bound) and e^(y + bound).
+        let y = from_signed(x.ln().unwrap());
+        let bound = tolerance(33) + half_unit();
+        let bx = from_unsigned(x.get_unsigned());
+        prop_assert!(reference_exp(&(&y - &bound)) <= bx);
+        prop_assert!(bx <= reference_exp(&(&y + &bound)));
+        prop_assert_eq!(x.get_unsigned().ln(), x.ln());
+    }
+
+    #[test]
+    fn unsigned_saturating_overflowing(x in unsigned(), y in unsigned()) {
+        let (bx, by) = (from_unsigned(x), from_unsigned(y));
+        let truncated = |exact: &BigRational| to_unsigned(exact, RoundingMode::Down);
+        prop_assert_eq!(x.saturating_add(y), saturate_unsigned(truncated(&(&bx + &by))));
+        prop_assert_eq!(x.saturating_sub(y), saturate_unsigned(truncated(&(&bx - &by))));
+        prop_assert_eq!(x.saturating_mul(y), saturate_unsigned(truncated(&(&bx * &by))));
+
+        let overflowing = |(value, overflowed): (UnsignedDecimal, bool)| {
+            (u128::from_le_bytes(value.to_bytes()), overflowed)
+        };
+        // Floor, rather than truncation, so that a negative difference wraps.
+        let floor_raw = |exact: &BigRational| (exact * multiplier()).floor().to_integer();
+        prop_assert_eq!(overflowing(x.overflowing_add(y)), wrap(&floor_raw(&(&bx + &by))));
+        prop_assert_eq!(overflowing(x.overflowing_sub(y)), wrap(&floor_raw(&(&bx - &by))));
+        prop_assert_eq!(overflowing(x.overflowing_mul(y)), wrap(&floor_raw(&(&bx * &by))));
+        if !by.is_zero() {
+            prop_assert_eq!(x.saturating_div(y), saturate_unsigned(truncated(&(&bx / &by))));
+            prop_assert_eq!(overflowing(x.overflowing_div(y)), wrap(&floor_raw(&(&bx / &by))));
+        }
+    }
+
+    #[test]
+    fn signed_saturating_overflowing(x in signed(), y in signed()) {
+        let (bx, by) = (from_signed(x), from_signed(y));
+        let saturated = |exact: &BigRational| match to_signed(exact, RoundingMode::TowardZero) {
+            Ok(value) => value,
+            Err(Error::Underflow) => SignedDecimal::MIN,
+            Err(_) => SignedDecimal::MAX,
+        };
+        // The magnitude wraps, and the sign is that of the exact result.
+        let overflowing = |exact: &BigRational| {
+            let (magnitude, overflowed) = wrap(&truncated_raw(exact).abs());
+            (SignedDecimal::from_raw_units(magnitude, exact.is_negative()), overflowed)
+        };
+        prop_assert_eq!(x.saturating_add(y), saturated(&(&bx + &by)));
+        prop_assert_eq!(x.saturating_sub(y), saturated(&(&bx - &by)));
+        prop_assert_eq!(x.saturating_mul(y), saturated(&(&bx * &by)));
+        prop_assert_eq!(x.overflowing_add(y), overflowing(&(&bx + &by)));
+        prop_assert_eq!(x.overflowing_sub(y), overflowing(&(&bx - &by)));
+        prop_assert_eq!(x.overflowing_mul(y), overflowing(&(&bx * &by)));
+        if !by.is_zero() {
+            prop_assert_eq!(x.saturating_div(y), saturated(&(&bx / &by)));
+            prop_assert_eq!(x.overflowing_div(y), overflowing(&(&bx / &by)));
+        }
+    }
+
+    #[test]
+    fn positive_saturating(x in any::<PositiveDecimal>(), y in any::<PositiveDecimal>()) {
+        let (bx, by) = (from_unsigned(x.get_unsigned()), from_unsigned(y.get_unsigned()));
+        let saturated = |exact: &BigRational| {
+            match to_unsigned(exact, RoundingMode::Down).and_then(PositiveDecimal::new) {
+                Ok(value) => value,
+                Err(Error::Overflow) => PositiveDecimal::MAX,
+                Err(_) => PositiveDecimal::MIN,
+            }
+        };
+        prop_assert_eq!(x.saturating_add(y), saturated(&(&bx + &by)));
+        prop_assert_eq!(x.saturating_sub(y), saturated(&(&bx - &by)));
+        prop_assert_eq!(x.saturating_mul(y), saturated(&(&bx * &by)));
+        prop_assert_eq!(x.saturating_div(y), saturated(&(&bx / &by)));
+    }
+
+    #[test]
+    fn widen_narrow(x in unsigned(), s in signed::<DEFAULT_SCALE>()) {
+        let bx = from_unsigned(x);
+        // Exact if the units fit, since no digits are dropped.
+        let widened = x.widen::<12>();
+        match widened {
+            Ok(w) => prop_assert_eq!(from_scaled(w), bx.clone()),
+            Err(e) => {
+                prop_assert_eq!(e, Error::Overflow);
+                prop_assert!(bx > from_scaled(ScaledUnsignedDecimal::<12>::MAX));
+            }
+        }
+        // Exact if the value has at most 2 fractional digits.
+        match x.narrow::<2>() {
+            Ok(n) => prop_assert_eq!(from_scaled(n), bx.clone()),
+            Err(e) => {
+                prop_assert_eq!(e, Error::PrecisionLoss);
+                prop_assert!(!(&bx * BigInt::from(100u32)).is_integer());
+            }
+        }
+        prop_assert_eq!(x.widen::<6>(), Ok(x));
+        prop_assert_eq!(x.narrow::<6>(), Ok(x));
+        if let Ok(p) = PositiveDecimal::new(x) {
+            prop_assert_eq!(p.widen::<12>().map(|w| w.get_unsigned()), widened);
+            prop_assert_eq!(p.narrow::<2>().map(|n| n.get_unsigned()), x.narrow::<2>());
+        }
+
+        // The signed types convert the magnitude, failing with Underflow
+        // rather than Overflow below MIN.
+        let magnitude = s.unsigned_abs();
+        let expected = match magnitude.widen::<12>() {
+            Err(Error::Overflow) if s.is_negative() => Err(Error::Underflow),
+            res => res,
+        };
+        prop_assert_eq!(s.widen::<12>().map(|w| w.unsigned_abs()), expected);
+        prop_assert_eq!(s.narrow::<2>().map(|n| n.unsigned_abs()), magnitude.narrow::<2>());
+        if let Ok(w) = s.widen::<12>() {
+            prop_assert_eq!(w.is_negative(), s.is_negative());
+        }
+    }
+
+    #[test]
+    fn from_f64(
+        value in prop_oneof![any::<f64>(), -1e34..1e34f64, -1e-4..1e-4f64],
+    ) {
+        for mode in MODES {
+            let Some(exact) = BigRational::from_float(value) else {
+                prop_assert_eq!(UnsignedDecimal::from_f64(value, mode), Err(Error::NotFinite));
+                prop_assert_eq!(SignedDecimal::from_f64(value, mode), Err(Error::NotFinite));
+                continue;
+            };
+            prop_assert_eq!(UnsignedDecimal::from_f64(value, mode), to_unsigned(&exact, mode));
+            prop_assert_eq!(
+                PositiveDecimal::from_f64(value, mode),
+                to_unsigned(&exact, mode).and_then(PositiveDecimal::new)
+            );
+            prop_assert_eq!(SignedDecimal::from_f64(value, mode), to_signed(&exact, mode));
+        }
+    }
+
+    #[test]
+    fn to_f64_lossy(x in unsigned(), s in signed()) {
+        // Within one unit in the last place of the nearest f64, so within
+        // 1.5 * 2^-52 relative to the exact value.
+        let bound = |exact: &BigRational| exact.abs() * BigRational::new(3.into(), BigInt::from(1u8) << 53);
+        let bx = from_unsigned(x);
+        let y = BigRational::from_float(x.to_f64_lossy()).unwrap();
+        prop_assert!((&y - &bx).abs() <= bound(&bx));
+        let bs = from_signed(s);
+        let y = BigRational::from_float(s.to_f64_lossy()).unwrap();
+        prop_assert!((&y - &bs).abs() <= bound(&bs));
+    }
+
+    #[test]
+    fn minor_units(x in unsigned(), units in unsigned::<DEFAULT_SCALE>(), decimals in 0..=48u32) {
+        let unit = BigInt::from(10u32).pow(decimals);
+        let minor = from_unsigned(x) * &unit;
+        let expected = if !minor.is_integer() {
+            Err(Error::PrecisionLoss)
+        } else {
+            u128::try_from(minor.to_integer()).map_err(|_| Error::Overflow)
+        };
+        prop_assert_eq!(x.to_minor_units(decimals), expected);
+        if let Ok(units) = expected {
+            prop_assert_eq!(UnsignedDecimal::from_minor_units(units, decimals), Ok(x));
+        }
+
+        let units = units.to_raw_units();
+        let exact = BigRational::new(units.into(), unit);
+        let expected = if (&exact * multiplier()).is_integer() {
+            to_unsigned(&exact, RoundingMode::Down)
+        } else {
+            Err(Error::PrecisionLoss)
+        };
+        prop_assert_eq!(UnsignedDecimal::from_minor_units(units, decimals), expected);
+    }
+
+    #[test]
+    fn mul_ratio(x in unsigned(), r in ratio()) {
+        let exact = from_unsigned(x) * from_unsigned(r.get_unsigned());
+        for mode in MODES {
+            prop_assert_eq!(Ok(x.mul_ratio(r, mode)), to_unsigned(&exact, mode));
+        }
+        // Multiplying by a ratio in any unit truncates.
+        let truncated = to_unsigned(&exact, RoundingMode::Down);
+        prop_assert_eq!(Ok(x * r), truncated);
+        prop_assert_eq!(Ok(x * Percent::from(r)), truncated);
+        prop_assert_eq!(Ok(x * BasisPoints::from(r)), truncated);
+
+        let br = from_unsigned(r.get_unsigned());
+        prop_assert_eq!(
+            from_unsigned(Percent::from(r).get_value()),
+            &br * BigInt::from(100u32)
+        );
+        prop_assert_eq!(
+            from_unsigned(BasisPoints::from(r).get_value()),
+            &br * BigInt::from(10_000u32)
+        );
+    }
+
+    #[test]
+    fn add_is_associative(
+        x in any::<UnsignedDecimal>(),
+        y in any::<UnsignedDecimal>(),
+        z in any::<UnsignedDecimal>(),
+    ) {
+        let left = x.checked_add(y).and_then(|xy| xy.checked_add(z));
+        let right = y.checked_add(z).and_then(|yz| x.checked_add(yz));
+        prop_assert_eq!(left, right);
+    }
+
+    #[test]
+    fn signed_add_is_associative(
+        x in any::<SignedDecimal>(),
+        y in any::<SignedDecimal>(),
+        z in any::<SignedDecimal>(),
+    ) {
+        // Intermediate results may overflow in one order but not the other.
+        let left = x.checked_add(y).and_then(|xy| xy.checked_add(z));
+        let right = y.checked_add(z).and_then(|yz| x.checked_add(yz));
+        if let (Ok(left), Ok(right)) = (left, right) {
+            prop_assert_eq!(left, right);
+        }
+    }
+
+    #[test]
+    fn display_round_trips(x in unsigned(), s in signed(), r in rational()) {
+        for mode in [ParseMode::Strict, ParseMode::Lenient] {
+            prop_assert_eq!(UnsignedDecimal::parse_with(&x.to_string(), mode), Ok(x));
+            prop_assert_eq!(SignedDecimal::parse_with(&s.to_string(), mode), Ok(s));
+            prop_assert_eq!(Rational::parse_with(&r.to_string(), mode), Ok(r));
+        }
+    }
+
+    #[test]
+    fn positive_is_never_zero(x in unsigned()) {
+        match PositiveDecimal::new(x) {
+            Ok(p) => prop_assert_eq!(p.get_unsigned(), x),
+            Err(e) => {
+                prop_assert_eq!(x, UnsignedDecimal::zero());
+                prop_assert_eq!(e, Error::Zero);
+            }
+        }
+    }
+
+    #[test]
+    fn signed_is_never_negative_zero(x in signed(), y in signed()) {
+        for value in [x.negate(), x.checked_mul(y).unwrap_or(x), x - x, x.checked_sub(y).unwrap_or(x)] {
+            if value == SignedDecimal::zero() {
+                prop_assert!(!value.is_negative());
+                prop_assert_eq!(value.to_bytes(), [0; 17]);
+            }
+        }
+    }
+}
@@ -2,6 +2,7 @@ use std::{collections::BTreeMap, sync::Arc};
 
 use anyhow::Result;
 use axum::{
+    body::Bytes,
     extract::State,
     http::{header::CONTENT_TYPE, Method, StatusCode},
     response::{IntoResponse, Response},
@@ -9,8 +10,9 @@ use axum::{
     Json, Router,
 };
 use common::{
-    BalanceResp, Euro, ListOwnersResp, MintFundsResp, Owner, PositiveAsset, PositiveDecimal, Price,
-    SellDollarsResp, SellEurosResp, ServerRequest, StatusResp, UnsignedAsset, Usd,
+    euro, swap, usd, BalanceResp, Euro, ListOwnersResp, MintFundsResp, NumericError, Owner,
+    PositiveAsset, Price, SellDollarsResp, SellEurosResp, ServerRequest, StatusResp, UnsignedAsset,
+    Usd,
 };
 use parking_lot::Mutex;
 use tower_http::cors::{Any, CorsLayer};
@@ -34,8 +36,8 @@ struct Balances {
 async fn main() {
     let app_state = AppState(Arc::new(Mutex::new(AppStateInner {
         accounts: BTreeMap::new(),
-        pool_usd: "103000USD".parse().unwrap(),
-        pool_euro: "100000EURO".parse().unwrap(),
+        pool_usd: usd!(103000),
+        pool_euro: euro!(100000),
     })));
 
     let cors = CorsLayer::new()
@@ -52,17 +54,65 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
-async fn handler(State(app): State<AppState>, Json(req): Json<ServerRequest>) -> impl IntoResponse {
-    match handler_inner(&app, req).await {
-        Ok(res) => res,
+// The body is decoded here rather than with the Json extractor, which would
+// reject a malformed amount with its own plain-text response.
+async fn handler(State(app): State<AppState>, body: Bytes) -> Response {
+    let req = match common::with_numeric_errors(|| serde_json::from_slice(&body)) {
+        Ok(req) => req,
         Err(e) => {
-            let mut res = Json(serde_json::json!({
-                "message": e.to_string()
-            }))
-            .into_response();
-            *res.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
-            res
+            let code = find_numeric_error(&e).map_or("invalid_request", numeric_error_code);
+            return error_response(StatusCode::BAD_REQUEST, code, &e);
         }
+    };
+    match handler_inner(&app, req).await {
+        Ok(res) => res,
+        // Numeric errors left in the chain are caused by the values in the
+        // request, e.g. selling more than the balance, so report them as
+        // client errors with a code the client can match on. Failures over
+        // the server's own state are converted with [internal].
+        Err(e) => match find_numeric_error(&e) {
+            Some(numeric) => {
+                error_response(StatusCode::BAD_REQUEST, numeric_error_code(numeric), &e)
+            }
+            None => error_response(StatusCode::INTERNAL_SERVER_ERROR, "internal", &e),
+        },
+    }
+}
+
+fn find_numeric_error(e: &anyhow::Error) -> Option<&NumericError> {
+    e.chain().find_map(|e| e.downcast_ref::<NumericError>())
+}
+
+fn error_response(status: StatusCode, code: &str, e: &anyhow::Error) -> Response {
+    let mut res = Json(serde_json::json!({
+        "message": e.to_string(),
+        "code": code,
+    }))
+    .into_response();
+    *res.status_mut() = status;
+    res
+}
+
+/// A numeric failure over the server's own state rather than the request,
+/// dropping the [NumericError] so that it is reported as an internal error.
+fn internal(e: NumericError) -> anyhow::Error {
+    anyhow::anyhow!("Invalid server state: {e}")
+}
+
+fn numeric_error_code(e: &NumericError) -> &'static str {
+    match e {
+        NumericError::Empty => "empty_number",
+        NumericError::InvalidDigit { .. } => "invalid_digit",
+        NumericError::TooManyFractionDigits { .. } => "too_many_fraction_digits",
+        NumericError::NegativeZero => "negative_zero",
+        NumericError::Overflow => "overflow",
+        NumericError::Underflow => "insufficient_funds",
+        NumericError::DivisionByZero => "division_by_zero",
+        NumericError::Zero => "zero_amount",
+        NumericError::PrecisionLoss => "precision_loss",
+        NumericError::MissingUnit { .. } => "missing_unit",
+        NumericError::InvalidEncoding => "invalid_encoding",
+        NumericError::NotFinite => "not_finite",
     }
 }
 
@@ -98,24 +148,30 @@ async fn handler_inner(app: &AppState, req: ServerRequest) -> Result<Response> {
 
 impl AppState {
     async fn status(&self) -> Result<StatusResp> {
-        let mut total_usd = UnsignedAsset::zero(Usd);
-        let mut total_euro = UnsignedAsset::zero(Euro);
-
         let guard = self.0.lock();
 
-        for balance in guard.accounts.values() {
-            total_usd += balance.usd;
-            total_euro += balance.euro;
-        }
-
-        total_usd += guard.pool_usd.into_unsigned();
-        total_euro += guard.pool_euro.into_unsigned();
+        let balances = guard.accounts.values();
+        let total_usd = UnsignedAsset::try_sum(
+            balances
+                .clone()
+                .map(|balance| balance.usd)
+                .chain([guard.pool_usd.into()]),
+        )
+        .map_err(internal)?;
+        let total_euro = UnsignedAsset::try_sum(
+            balances
+                .map(|balance| balance.euro)
+                .chain([guard.pool_euro.into()]),
+        )
+        .map_err(internal)?;
 
         Ok(StatusResp {
             total_usd,
             total_euro,
-            price_usd: Price::from_asset_ratios(guard.pool_usd, guard.pool_euro),
-            price_euro: Price::from_asset_ratios(guard.pool_euro, guard.pool_usd),
+            price_usd: Price::from_asset_ratios(guard.pool_usd, guard.pool_euro)
+                .map_err(internal)?,
+            price_euro: Price::from_asset_ratios(guard.pool_euro, guard.pool_usd)
+                .map_err(internal)?,
         })
     }
     async fn balance(&self, owner: &Owner) -> Result<BalanceResp> {
@@ -136,8 +192,10 @@ impl AppState {
     ) -> Result<MintFundsResp> {
         let mut guard = self.0.lock();
         let owner = guard.accounts.entry(recipient).or_default();
-        owner.usd += usd_amount;
-        owner.euro += euro_amount;
+        let usd = owner.usd.checked_add(usd_amount)?;
+        let euro = owner.euro.checked_add(euro_amount)?;
+        owner.usd = usd;
+        owner.euro = euro;
         Ok(MintFundsResp {})
     }
 
@@ -146,30 +204,27 @@ impl AppState {
         trader: Owner,
         dollars: PositiveAsset<Usd>,
     ) -> Result<SellDollarsResp> {
-        // Pool has a constant, K
-        // K = total USD in pool * total EURO in pool
-        // If you buy or sell, the value K must remain the same
+        // All calculations are checked and performed before any state is
+        // modified, so a failure leaves both the pool and the trader untouched.
+        // See [swap] for the pool's side, including the rounding.
         let mut guard = self.0.lock();
 
-        let mut pool_usd = guard.pool_usd;
+        let pool_usd = guard.pool_usd;
         let pool_euro = guard.pool_euro;
         let owner = guard.accounts.entry(trader).or_default();
-        owner.usd.checked_sub_assign(dollars.into_unsigned())?;
-
-        let k = pool_usd.into_unsigned().into_decimal() * pool_euro.into_unsigned().into_decimal();
-
-        pool_usd += dollars;
-        let new_pool_euro = k / pool_usd.into_unsigned().into_decimal();
-        let new_pool_euro = PositiveAsset::new(Euro, PositiveDecimal::new(new_pool_euro)?);
-
-        let euros_bought = pool_euro.checked_sub(new_pool_euro)?;
+        let owner_usd = owner.usd.checked_sub(dollars.into_unsigned())?;
 
-        owner.euro += euros_bought.into_unsigned();
+        let trade = swap(pool_usd, pool_euro, dollars)?;
+        let owner_euro = owner.euro.checked_add(trade.bought.into_unsigned())?;
 
-        guard.pool_usd = pool_usd;
-        guard.pool_euro = new_pool_euro;
+        owner.usd = owner_usd;
+        owner.euro = owner_euro;
+        guard.pool_usd = trade.pool_sold;
+        guard.pool_euro = trade.pool_bought;
 
-        Ok(SellDollarsResp { euros_bought })
+        Ok(SellDollarsResp {
+            euros_bought: trade.bought,
+        })
     }
 
     async fn sell_euros(&self, trader: Owner, euros: PositiveAsset<Euro>) -> Result<SellEurosResp> {
@@ -177,24 +232,21 @@ impl AppState {
         let mut guard = self.0.lock();
 
         let pool_usd = guard.pool_usd;
-        let mut pool_euro = guard.pool_euro;
+        let pool_euro = guard.pool_euro;
         let owner = guard.accounts.entry(trader).or_default();
-        owner.euro.checked_sub_assign(euros.into_unsigned())?;
-
-        let k = pool_usd.into_unsigned().into_decimal() * pool_euro.into_unsigned().into_decimal();
+        let owner_euro = owner.euro.checked_sub(euros.into_unsigned())?;
 
-        pool_euro += euros;
-        let new_pool_dollar = k / pool_euro.into_unsigned().into_decimal();
-        let new_pool_dollar = PositiveAsset::new(Usd, PositiveDecimal::new(new_pool_dollar)?);
+        let trade = swap(pool_euro, pool_usd, euros)?;
+        let owner_usd = owner.usd.checked_add(trade.bought.into_unsigned())?;
 
-        let dollars_bought = pool_usd.checked_sub(new_pool_dollar)?;
+        owner.usd = owner_usd;
+        owner.euro = owner_euro;
+        guard.pool_usd = trade.pool_bought;
+        guard.pool_euro = trade.pool_sold;
 
-        owner.usd += dollars_bought.into_unsigned();
-
-        guard.pool_usd = new_pool_dollar;
-        guard.pool_euro = pool_euro;
-
-        Ok(SellEurosResp { dollars_bought })
+        Ok(SellEurosResp {
+            dollars_bought: trade.bought,
+        })
     }
 
     async fn list_owners(&self, start_after: Option<Owner>) -> Result<ListOwnersResp> {